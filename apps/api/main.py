@@ -3,11 +3,26 @@ from fastapi.middleware.cors import CORSMiddleware
 from pydantic import BaseModel, Field, EmailStr
 from typing import Optional, List
 from datetime import datetime
+from decimal import Decimal
+import base64
+import hashlib
+import json
 import os
+import secrets
+import struct
+import time
 from contextlib import asynccontextmanager
 import asyncpg
 from asyncpg.pool import Pool
 from dotenv import load_dotenv
+from solana.rpc.async_api import AsyncClient
+from solana.rpc.commitment import Confirmed
+from solders.instruction import AccountMeta, Instruction
+from solders.keypair import Keypair
+from solders.message import Message
+from solders.pubkey import Pubkey
+from solders.system_program import ID as SYSTEM_PROGRAM_ID
+from solders.transaction import Transaction
 
 # Load environment variables from .env file
 load_dotenv()
@@ -20,6 +35,23 @@ DATABASE_URL = os.getenv("DATABASE_URL")
 if not DATABASE_URL:
     raise ValueError("DATABASE_URL environment variable is required")
 
+# Solana game server: records won games on-chain and signs mint attestations
+# for the planet-nft program. GAME_SERVER_KEYPAIR is the secret key JSON array
+# written by `solana-keygen`, and must be the config's attestation_signer.
+SOLANA_RPC_URL = os.getenv("SOLANA_RPC_URL", "https://api.devnet.solana.com")
+PLANET_NFT_PROGRAM_ID = Pubkey.from_string(
+    os.getenv("PLANET_NFT_PROGRAM_ID", "Fb7uNXapsRwUdsvGDedesLS7D1A4AHk6CeMvrrvTVqwf")
+)
+GAME_SERVER_KEYPAIR = os.getenv("GAME_SERVER_KEYPAIR")
+game_server: Optional[Keypair] = (
+    Keypair.from_bytes(bytes(json.loads(GAME_SERVER_KEYPAIR)))
+    if GAME_SERVER_KEYPAIR
+    else None
+)
+
+# How long a signed mint attestation stays valid
+MINT_ATTESTATION_TTL_SECONDS = 600
+
 # ============================================================================
 # Database Connection Pool
 # ============================================================================
@@ -212,6 +244,13 @@ class PlanetNFTCreate(BaseModel):
     player_email: EmailStr = Field(..., description="Player email address")
     planet_id: str = Field(..., description="Unique planet identifier")
     planet_name: str = Field(..., description="Name of the planet")
+    seed: Optional[int] = Field(
+        None, ge=0, lt=2**64, description="planet-generator seed of the planet"
+    )
+    planet_color: Optional[str] = Field(None, description="Planet color")
+    avg_temp: Optional[str] = Field(None, description="Average temperature, e.g. '72°F'")
+    ocean_coverage: Optional[str] = Field(None, description="Ocean coverage, e.g. '45%'")
+    gravity: Optional[str] = Field(None, description="Gravity, e.g. '1.12g'")
 
 
 class PlanetNFTResponse(BaseModel):
@@ -236,6 +275,37 @@ class UpdateMintInfoRequest(BaseModel):
     metadata_uri: str = Field(..., description="IPFS/Arweave metadata URL")
 
 
+class MintAttestationRequest(BaseModel):
+    """Request model for a mint attestation"""
+
+    wallet: str = Field(..., description="Base58 wallet the planet is minted to")
+
+
+class PlanetStatsResponse(BaseModel):
+    """Planet attributes in the planet-nft program's PlanetStats layout"""
+
+    temperature_f: int
+    ocean_coverage: int
+    gravity_centi_g: int
+    color: str
+
+
+class MintAttestationResponse(BaseModel):
+    """mint_planet_nft args plus the game server's signature over them.
+    u64 fields are decimal strings, since they don't fit a JS number."""
+
+    planet_id: str
+    seed: str
+    planet_name: str
+    metadata_uri: str
+    game_id: str
+    expiry: int
+    nonce: str
+    soulbound: bool
+    stats: PlanetStatsResponse
+    signature: str = Field(..., description="Base64 ed25519 signature")
+
+
 class MetadataUploadRequest(BaseModel):
     """Request model for uploading NFT metadata"""
 
@@ -555,24 +625,35 @@ async def get_researcher_chat_logs(
 
 async def earn_planet_nft(
     conn: asyncpg.Connection,
-    email: str,
-    planet_id: str,
-    planet_name: str,
+    nft_data: PlanetNFTCreate,
 ) -> dict:
     """Create NFT record when player earns a planet, handles duplicates with ON CONFLICT"""
     nft = await conn.fetchrow(
         """
-        INSERT INTO planet_nfts (player_email, planet_id, planet_name, earned_date)
-        VALUES ($1, $2, $3, CURRENT_TIMESTAMP)
+        INSERT INTO planet_nfts (
+            player_email, planet_id, planet_name, earned_date,
+            seed, planet_color, avg_temp, ocean_coverage, gravity
+        )
+        VALUES ($1, $2, $3, CURRENT_TIMESTAMP, $4, $5, $6, $7, $8)
         ON CONFLICT (player_email, planet_id) 
         DO UPDATE SET 
             planet_name = EXCLUDED.planet_name,
+            seed = EXCLUDED.seed,
+            planet_color = EXCLUDED.planet_color,
+            avg_temp = EXCLUDED.avg_temp,
+            ocean_coverage = EXCLUDED.ocean_coverage,
+            gravity = EXCLUDED.gravity,
             updated_at = CURRENT_TIMESTAMP
         RETURNING id, player_email, planet_id, planet_name, earned_date, minted, token_id, mint_signature, metadata_uri
         """,
-        email,
-        planet_id,
-        planet_name,
+        nft_data.player_email,
+        nft_data.planet_id,
+        nft_data.planet_name,
+        None if nft_data.seed is None else Decimal(nft_data.seed),
+        nft_data.planet_color,
+        nft_data.avg_temp,
+        nft_data.ocean_coverage,
+        nft_data.gravity,
     )
     return dict(nft)
 
@@ -658,6 +739,33 @@ async def get_nft_by_id(
     return dict(nft)
 
 
+async def bind_nft_wallet(
+    conn: asyncpg.Connection,
+    nft_id: int,
+    wallet: str,
+) -> dict:
+    """Tie an unminted NFT to the wallet it will be minted to. The first wallet
+    to ask wins, since its on-chain game session is created for that wallet."""
+    nft = await conn.fetchrow(
+        """
+        UPDATE planet_nfts
+        SET wallet_address = $2
+        WHERE id = $1
+            AND minted = FALSE
+            AND (wallet_address IS NULL OR wallet_address = $2)
+        RETURNING id, planet_id, planet_name, seed, planet_color, avg_temp, ocean_coverage, gravity
+        """,
+        nft_id,
+        wallet,
+    )
+    if not nft:
+        await get_nft_by_id(conn, nft_id)  # 404 if it doesn't exist
+        raise HTTPException(
+            status_code=409, detail="NFT is already minted or bound to another wallet"
+        )
+    return dict(nft)
+
+
 async def get_all_game_sessions(
     conn: asyncpg.Connection,
     email: str,
@@ -750,6 +858,121 @@ async def get_game_session_with_chats(
     }
 
 
+# ============================================================================
+# Solana Game Server
+# ============================================================================
+
+
+def find_program_pda(*seeds: bytes) -> Pubkey:
+    """PDA of the planet-nft program"""
+    return Pubkey.find_program_address(list(seeds), PLANET_NFT_PROGRAM_ID)[0]
+
+
+def borsh_string(value: str) -> bytes:
+    """Borsh string: u32 LE length, then the UTF-8 bytes"""
+    data = value.encode()
+    return struct.pack("<I", len(data)) + data
+
+
+def anchor_instruction(name: str, args: bytes, accounts: List[AccountMeta]) -> Instruction:
+    """planet-nft instruction: Anchor discriminator followed by Borsh args"""
+    discriminator = hashlib.sha256(f"global:{name}".encode()).digest()[:8]
+    return Instruction(PLANET_NFT_PROGRAM_ID, discriminator + args, accounts)
+
+
+def metadata_uri_for(planet_id: str) -> str:
+    """Metadata URI of a planet (placeholder until IPFS/Arweave upload exists)"""
+    return f"https://placeholder.metadata/{planet_id}"
+
+
+def planet_stats(nft: dict) -> PlanetStatsResponse:
+    """PlanetStats from the display strings the client stored, e.g. '72°F'"""
+    return PlanetStatsResponse(
+        temperature_f=int(nft["avg_temp"].removesuffix("°F")),
+        ocean_coverage=int(nft["ocean_coverage"].removesuffix("%")),
+        gravity_centi_g=round(float(nft["gravity"].removesuffix("g")) * 100),
+        color=nft["planet_color"],
+    )
+
+
+async def record_won_game(
+    server: Keypair, player: Pubkey, game_id: int, seed: int
+) -> None:
+    """Create the player's on-chain GameSession for a won game, with the planet
+    they picked, unless an earlier attestation request already did"""
+    config = find_program_pda(b"config")
+    game_session = find_program_pda(
+        b"game_session", bytes(player), struct.pack("<Q", game_id)
+    )
+    session_accounts = [
+        AccountMeta(config, is_signer=False, is_writable=False),
+        AccountMeta(server.pubkey(), is_signer=True, is_writable=False),
+        AccountMeta(player, is_signer=False, is_writable=False),
+        AccountMeta(game_session, is_signer=False, is_writable=True),
+    ]
+
+    async with AsyncClient(SOLANA_RPC_URL, commitment=Confirmed) as client:
+        if (await client.get_account_info(game_session)).value is not None:
+            return
+
+        start_session = anchor_instruction(
+            "start_session",
+            struct.pack("<Q", game_id),
+            session_accounts
+            + [
+                AccountMeta(server.pubkey(), is_signer=True, is_writable=True),  # payer
+                AccountMeta(SYSTEM_PROGRAM_ID, is_signer=False, is_writable=False),
+            ],
+        )
+        # Wins can only be ended by revealing a commitment made in the slot
+        # the session started in. The game's planets are generated in the
+        # browser, so all the server knows is the won planet: it commits to a
+        # one-planet game with researcher 0 and reveals it right away.
+        salt = secrets.token_bytes(32)
+        commitment = hashlib.sha256(bytes([0]) + struct.pack("<Q", seed) + salt).digest()
+        commit_game = anchor_instruction(
+            "commit_game",
+            struct.pack("<Q", game_id) + commitment,
+            session_accounts,
+        )
+        # RevealGameArgs: game_id, researcher_index, planet_seeds, salt,
+        # selected_researcher
+        reveal_game = anchor_instruction(
+            "reveal_game",
+            struct.pack("<QBIQ", game_id, 0, 1, seed) + salt + bytes([0]),
+            session_accounts,
+        )
+
+        blockhash = (await client.get_latest_blockhash()).value.blockhash
+        transaction = Transaction(
+            [server],
+            Message([start_session, commit_game, reveal_game], server.pubkey()),
+            blockhash,
+        )
+        signature = (await client.send_transaction(transaction)).value
+        await client.confirm_transaction(signature, Confirmed)
+
+
+def encode_mint_attestation(
+    planet_id: str,
+    recipient: Pubkey,
+    game_id: int,
+    seed: int,
+    metadata_uri: str,
+    expiry: int,
+    nonce: int,
+    soulbound: bool,
+) -> bytes:
+    """Borsh encoding of the program's MintAttestation struct"""
+    return (
+        borsh_string(planet_id)
+        + bytes(recipient)
+        + struct.pack("<QQ", game_id, seed)
+        + borsh_string(metadata_uri)
+        + struct.pack("<qQ?", expiry, nonce, soulbound)
+    )
+
+
 # ============================================================================
 # API Endpoints
 # ============================================================================
@@ -960,9 +1183,7 @@ async def get_researcher_logs(email: str, researcher_name: str, conn=Depends(get
 async def earn_nft(nft_data: PlanetNFTCreate, conn=Depends(get_db)):
     """Earn an NFT (called when player wins)"""
     try:
-        nft = await earn_planet_nft(
-            conn, nft_data.player_email, nft_data.planet_id, nft_data.planet_name
-        )
+        nft = await earn_planet_nft(conn, nft_data)
         return PlanetNFTResponse(**nft)
     except Exception as e:
         raise HTTPException(
@@ -1012,6 +1233,57 @@ async def update_mint_info(
         )
 
 
+@app.post("/api/nfts/{nft_id}/attestation", response_model=MintAttestationResponse)
+async def mint_attestation(
+    nft_id: int, request: MintAttestationRequest, conn=Depends(get_db)
+):
+    """Record the win behind an earned NFT on-chain and sign the attestation
+    mint_planet_nft needs. The NFT's id doubles as the on-chain game_id."""
+    if game_server is None:
+        raise HTTPException(status_code=503, detail="Game server key not configured")
+    try:
+        recipient = Pubkey.from_string(request.wallet)
+    except ValueError:
+        raise HTTPException(status_code=422, detail="Invalid wallet address")
+
+    try:
+        nft = await bind_nft_wallet(conn, nft_id, request.wallet)
+        if nft["seed"] is None or nft["avg_temp"] is None:
+            raise HTTPException(
+                status_code=409, detail="NFT was earned without a planet seed and stats"
+            )
+        seed = int(nft["seed"])
+
+        await record_won_game(game_server, recipient, nft_id, seed)
+
+        metadata_uri = metadata_uri_for(nft["planet_id"])
+        expiry = int(time.time()) + MINT_ATTESTATION_TTL_SECONDS
+        nonce = secrets.randbits(64)
+        message = encode_mint_attestation(
+            nft["planet_id"], recipient, nft_id, seed, metadata_uri, expiry, nonce, False
+        )
+        signature = game_server.sign_message(message)
+
+        return MintAttestationResponse(
+            planet_id=nft["planet_id"],
+            seed=str(seed),
+            planet_name=nft["planet_name"],
+            metadata_uri=metadata_uri,
+            game_id=str(nft_id),
+            expiry=expiry,
+            nonce=str(nonce),
+            soulbound=False,
+            stats=planet_stats(nft),
+            signature=base64.b64encode(bytes(signature)).decode(),
+        )
+    except HTTPException:
+        raise
+    except Exception as e:
+        raise HTTPException(
+            status_code=500, detail=f"Failed to create mint attestation: {str(e)}"
+        )
+
+
 @app.get("/api/nfts/{nft_id}", response_model=PlanetNFTResponse)
 async def get_nft_by_id_endpoint(nft_id: int, conn=Depends(get_db)):
     """Get NFT by ID"""
@@ -1030,10 +1302,7 @@ async def get_nft_by_id_endpoint(nft_id: int, conn=Depends(get_db)):
 async def upload_metadata(metadata: MetadataUploadRequest, conn=Depends(get_db)):
     """Upload metadata and return URI (placeholder implementation)"""
     try:
-        # For now, return placeholder URI
-        # Later can integrate with IPFS/Arweave
-        uri = f"https://placeholder.metadata/{metadata.planet_id}"
-        return {"uri": uri}
+        return {"uri": metadata_uri_for(metadata.planet_id)}
     except Exception as e:
         raise HTTPException(
             status_code=500, detail=f"Failed to upload metadata: {str(e)}"
@@ -0,0 +1,12 @@
+-- Migration: Store what the game server needs to attest planet NFT mints
+-- Run this migration after 001_create_planet_nfts.sql
+
+-- Planet the NFT is for, as generated by planet-generator, and the wallet the
+-- game server recorded the win for on-chain
+ALTER TABLE planet_nfts
+    ADD COLUMN IF NOT EXISTS seed NUMERIC(20, 0) NULL,
+    ADD COLUMN IF NOT EXISTS planet_color VARCHAR(255) NULL,
+    ADD COLUMN IF NOT EXISTS avg_temp VARCHAR(32) NULL,
+    ADD COLUMN IF NOT EXISTS ocean_coverage VARCHAR(32) NULL,
+    ADD COLUMN IF NOT EXISTS gravity VARCHAR(32) NULL,
+    ADD COLUMN IF NOT EXISTS wallet_address VARCHAR(64) NULL;
@@ -0,0 +1,49 @@
+//! WebAssembly bindings to `planet-generator` for the web client. Build with
+//! `wasm-pack build crates/planet-generator-wasm --target web`.
+//!
+//! Getters return the same display strings as the web client's `Voice`
+//! fields (e.g. "72°F", "45%", "1.12g").
+
+use planet_generator::Planet;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(js_name = GeneratedPlanet)]
+pub struct WasmPlanet(Planet);
+
+#[wasm_bindgen(js_class = GeneratedPlanet)]
+impl WasmPlanet {
+    #[wasm_bindgen(getter, js_name = planetName)]
+    pub fn planet_name(&self) -> String {
+        self.0.name.to_string()
+    }
+
+    #[wasm_bindgen(getter, js_name = planetColor)]
+    pub fn planet_color(&self) -> String {
+        self.0.color.to_string()
+    }
+
+    #[wasm_bindgen(getter, js_name = avgTemp)]
+    pub fn avg_temp(&self) -> String {
+        format!("{}°F", self.0.temperature_f)
+    }
+
+    #[wasm_bindgen(getter, js_name = oceanCoverage)]
+    pub fn ocean_coverage(&self) -> String {
+        format!("{}%", self.0.ocean_coverage)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn gravity(&self) -> String {
+        format!(
+            "{}.{:02}g",
+            self.0.gravity_centi_g / 100,
+            self.0.gravity_centi_g % 100
+        )
+    }
+}
+
+/// Takes a `bigint` seed on the JS side
+#[wasm_bindgen(js_name = generatePlanet)]
+pub fn generate_planet(seed: u64) -> WasmPlanet {
+    WasmPlanet(planet_generator::generate_planet(seed))
+}
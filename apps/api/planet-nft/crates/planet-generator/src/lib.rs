@@ -0,0 +1,265 @@
+//! Deterministic planet generator.
+//!
+//! Turns a seed into a planet's name, color and stats, using the tables and
+//! ranges the web client's `generateRandomPlanets` started with. The same seed
+//! always gives the same planet, so the program, the API and the web client
+//! can all re-derive a planet instead of trusting whoever reports it.
+//!
+//! The crate is `no_std` and allocation free so the program can use it. The
+//! web client uses it through `planet-generator-wasm`.
+
+#![no_std]
+
+use core::fmt;
+
+pub const PREFIXES: [&str; 25] = [
+    "Ery", "Vel", "Thry", "Kal", "Zen", "Sol", "Pra", "Isc", "Typh", "Nex", "Vor", "Kry", "Lum",
+    "Ast", "Orb", "Zar", "Dra", "Qua", "Xen", "Pyr", "Neb", "Aur", "Ceph", "Peg", "Lyr",
+];
+
+pub const MIDDLES: [&str; 24] = [
+    "thos", "kara", "on", "mora", "thara", "uneth", "vax", "alon", "ara", "ion", "ath", "oss",
+    "ina", "rex", "dus", "nor", "pex", "tis", "gon", "lius", "mar", "tec", "dor", "lux",
+];
+
+pub const SUFFIXES: [&str; 18] = [
+    "Prime", "7", "Delta", "Ridge", "IX", "Station", "Alpha", "Beta", "Minor", "Major", "Gamma",
+    "Sigma", "III", "V", "X", "Outpost", "Nexus", "Haven",
+];
+
+pub const COLORS: [&str; 30] = [
+    "Deep orange with red streaks",
+    "Bluish green",
+    "Pale icy teal",
+    "Jungle green with gold clouds",
+    "Rust red",
+    "Steel blue with white ridges",
+    "Bright emerald",
+    "Soft lavender",
+    "Indigo with shimmering frost bands",
+    "Crimson with dark patches",
+    "Golden yellow with brown swirls",
+    "Deep purple with silver highlights",
+    "Turquoise with white clouds",
+    "Burnt orange with black streaks",
+    "Mint green with cyan bands",
+    "Rose pink with violet tints",
+    "Charcoal gray with red veins",
+    "Cobalt blue with ice caps",
+    "Amber with bronze clouds",
+    "Seafoam green with blue oceans",
+    "Magenta with purple hazes",
+    "Copper orange with dark swirls",
+    "Sapphire blue with white storms",
+    "Lime green with yellow patches",
+    "Maroon red with black craters",
+    "Aquamarine with silver streaks",
+    "Slate gray with blue tints",
+    "Coral pink with orange bands",
+    "Navy blue with white ice",
+    "Olive green with brown continents",
+];
+
+pub const MAX_TEMPERATURE_F: u8 = 120;
+pub const MAX_OCEAN_COVERAGE: u8 = 95;
+pub const MIN_GRAVITY_CENTI_G: u16 = 50;
+pub const MAX_GRAVITY_CENTI_G: u16 = 150;
+
+/// Planet name built from the name tables, e.g. "Velkara", "Velkara Prime"
+/// or "Vel Prime"
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlanetName {
+    pub prefix: &'static str,
+    pub middle: Option<&'static str>,
+    pub suffix: Option<&'static str>,
+}
+
+impl fmt::Display for PlanetName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.prefix)?;
+        if let Some(middle) = self.middle {
+            f.write_str(middle)?;
+        }
+        if let Some(suffix) = self.suffix {
+            write!(f, " {}", suffix)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Planet {
+    pub name: PlanetName,
+    pub color: &'static str,
+    pub temperature_f: u8,
+    pub ocean_coverage: u8,
+    pub gravity_centi_g: u16,
+}
+
+/// Generates the planet for `seed`. The draw order is part of the format:
+/// changing it changes every planet.
+pub fn generate_planet(seed: u64) -> Planet {
+    let mut rng = SplitMix64(seed);
+
+    let prefix = PREFIXES[rng.below(PREFIXES.len())];
+    let middle = MIDDLES[rng.below(MIDDLES.len())];
+    let suffix = SUFFIXES[rng.below(SUFFIXES.len())];
+    // Same mix as the web client: 40% prefix + middle, 40% prefix + middle
+    // + suffix, 20% prefix + suffix
+    let name = match rng.below(10) {
+        0..=3 => PlanetName {
+            prefix,
+            middle: Some(middle),
+            suffix: None,
+        },
+        4..=7 => PlanetName {
+            prefix,
+            middle: Some(middle),
+            suffix: Some(suffix),
+        },
+        _ => PlanetName {
+            prefix,
+            middle: None,
+            suffix: Some(suffix),
+        },
+    };
+
+    let temperature_f = rng.below(MAX_TEMPERATURE_F as usize + 1) as u8;
+    let ocean_coverage = rng.below(MAX_OCEAN_COVERAGE as usize + 1) as u8;
+    let gravity_centi_g = MIN_GRAVITY_CENTI_G
+        + rng.below((MAX_GRAVITY_CENTI_G - MIN_GRAVITY_CENTI_G) as usize + 1) as u16;
+    let color = COLORS[rng.below(COLORS.len())];
+
+    Planet {
+        name,
+        color,
+        temperature_f,
+        ocean_coverage,
+        gravity_centi_g,
+    }
+}
+
+/// SplitMix64, small enough to port to any client that needs to reproduce
+/// a planet without this crate
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform index in `0..n`, the equivalent of
+    /// `Math.floor(Math.random() * n)`
+    fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    // Minted planets store only their seed, so these must never change. The
+    // TS tests use the same vectors.
+    const GOLDEN: [(u64, &str, &str, u8, u8, u16); 6] = [
+        (
+            0,
+            "Ceph Prime",
+            "Lime green with yellow patches",
+            12,
+            31,
+            67,
+        ),
+        (
+            1,
+            "Orbtis Haven",
+            "Rose pink with violet tints",
+            53,
+            73,
+            138,
+        ),
+        (7, "Nexthos Nexus", "Crimson with dark patches", 54, 23, 97),
+        (42, "Xenmora", "Maroon red with black craters", 4, 83, 72),
+        (1337, "Qualius Ridge", "Pale icy teal", 27, 63, 87),
+        (u64::MAX, "Cephtec Ridge", "Soft lavender", 85, 79, 145),
+    ];
+
+    const SAMPLES: u64 = 20_000;
+
+    #[test]
+    fn golden_planets() {
+        for (seed, name, color, temperature_f, ocean_coverage, gravity_centi_g) in GOLDEN {
+            let planet = generate_planet(seed);
+            assert_eq!(planet.name.to_string(), name, "seed {}", seed);
+            assert_eq!(planet.color, color, "seed {}", seed);
+            assert_eq!(planet.temperature_f, temperature_f, "seed {}", seed);
+            assert_eq!(planet.ocean_coverage, ocean_coverage, "seed {}", seed);
+            assert_eq!(planet.gravity_centi_g, gravity_centi_g, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn stats_stay_in_range() {
+        let seeds = (0..SAMPLES).chain((0..SAMPLES).map(|i| u64::MAX - i));
+        for seed in seeds {
+            let planet = generate_planet(seed);
+            assert!(planet.temperature_f <= MAX_TEMPERATURE_F, "seed {}", seed);
+            assert!(planet.ocean_coverage <= MAX_OCEAN_COVERAGE, "seed {}", seed);
+            assert!(
+                (MIN_GRAVITY_CENTI_G..=MAX_GRAVITY_CENTI_G).contains(&planet.gravity_centi_g),
+                "seed {}",
+                seed
+            );
+            assert!(COLORS.contains(&planet.color), "seed {}", seed);
+            assert!(PREFIXES.contains(&planet.name.prefix), "seed {}", seed);
+            assert!(planet.name.middle.is_some() || planet.name.suffix.is_some());
+            if let Some(middle) = planet.name.middle {
+                assert!(MIDDLES.contains(&middle), "seed {}", seed);
+            }
+            if let Some(suffix) = planet.name.suffix {
+                assert!(SUFFIXES.contains(&suffix), "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn reaches_both_ends_of_every_table_and_range() {
+        let mut prefixes = [false; PREFIXES.len()];
+        let mut colors = [false; COLORS.len()];
+        let mut temperatures = [false; MAX_TEMPERATURE_F as usize + 1];
+        let mut gravities = (u16::MAX, 0);
+        for seed in 0..SAMPLES {
+            let planet = generate_planet(seed);
+            prefixes[PREFIXES
+                .iter()
+                .position(|p| *p == planet.name.prefix)
+                .unwrap()] = true;
+            colors[COLORS.iter().position(|c| *c == planet.color).unwrap()] = true;
+            temperatures[planet.temperature_f as usize] = true;
+            gravities = (
+                gravities.0.min(planet.gravity_centi_g),
+                gravities.1.max(planet.gravity_centi_g),
+            );
+        }
+        assert!(prefixes.iter().all(|seen| *seen));
+        assert!(colors.iter().all(|seen| *seen));
+        assert!(temperatures.iter().all(|seen| *seen));
+        assert_eq!(gravities, (MIN_GRAVITY_CENTI_G, MAX_GRAVITY_CENTI_G));
+    }
+
+    #[test]
+    fn below_stays_under_n() {
+        let mut rng = SplitMix64(u64::MAX);
+        for n in 1..1_000 {
+            assert!(rng.below(n) < n);
+        }
+        assert_eq!(SplitMix64(0).below(1), 0);
+    }
+}
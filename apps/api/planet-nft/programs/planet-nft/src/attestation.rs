@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::error::ErrorCode;
+
+// Layout of the ed25519 program's instruction data (see
+// solana_sdk::ed25519_instruction): a u8 signature count, a padding byte, then
+// one 14-byte offsets struct per signature.
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+
+/// Message the game server signs when a player wins a planet. Borsh-encoded,
+/// so the backend must serialize the fields in this order.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MintAttestation {
+    pub planet_id: String,
+    pub recipient: Pubkey,
+    /// Game session the planet was won in
+    pub game_id: u64,
+    /// `planet-generator` seed of the planet picked in that game
+    pub seed: u64,
+    pub metadata_uri: String,
+    pub expiry: i64,
+    pub nonce: u64,
+    /// Planet is frozen in the recipient's wallet after minting
+    pub soulbound: bool,
+}
+
+/// Message the game server signs for a finished game, so the result can be
+/// added to the player's on-chain stats. Borsh-encoded like
+/// `MintAttestation`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GameResultAttestation {
+    pub player: Pubkey,
+    pub game_id: u64,
+    pub correct_guesses: u32,
+    pub correct_ejections: u32,
+    pub incorrect_guesses: u32,
+    pub incorrect_ejections: u32,
+}
+
+/// Checks that the instruction right before the current one is an ed25519
+/// signature verification of `message` by `expected_signer`. The ed25519
+/// program itself rejects the transaction if the signature is invalid, so we
+/// only need to make sure it checked the key and message we care about.
+pub fn verify_ed25519_attestation(
+    instructions_sysvar: &AccountInfo,
+    expected_signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::MissingAttestation);
+
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    require!(
+        ix.program_id == ed25519_program::ID && ix.accounts.is_empty(),
+        ErrorCode::MissingAttestation
+    );
+
+    let data = &ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE && data[0] == 1,
+        ErrorCode::MissingAttestation
+    );
+
+    let read_u16 = |offset: usize| {
+        u16::from_le_bytes([
+            data[SIGNATURE_OFFSETS_START + offset],
+            data[SIGNATURE_OFFSETS_START + offset + 1],
+        ])
+    };
+    let signature_instruction_index = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_instruction_index = read_u16(6);
+    let message_data_offset = read_u16(8) as usize;
+    let message_data_size = read_u16(10) as usize;
+    let message_instruction_index = read_u16(12);
+
+    // All of the data must live in the ed25519 instruction itself, otherwise
+    // the signature could be over bytes from some other instruction
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        ErrorCode::MissingAttestation
+    );
+
+    let signer = data
+        .get(public_key_offset..public_key_offset + PUBKEY_SIZE)
+        .ok_or(ErrorCode::MissingAttestation)?;
+    require!(
+        signer == expected_signer.as_ref(),
+        ErrorCode::InvalidAttestationSigner
+    );
+
+    let signed_message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(ErrorCode::MissingAttestation)?;
+    require!(signed_message == message, ErrorCode::AttestationMismatch);
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+/// Mints used to be PDAs of the planet_id alone, which only allowed one copy
+/// of each planet. Still needed to find those mints for migration.
+#[constant]
+pub const LEGACY_MINT_SEED: &[u8] = b"planet_nft";
+
+#[constant]
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+
+#[constant]
+pub const CONFIG_SEED: &[u8] = b"config";
+
+#[constant]
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+#[constant]
+pub const PLAYER_PLANET_SEED: &[u8] = b"player_planet";
+
+/// Program-wide PDA that is the update authority of the planets collection
+#[constant]
+pub const AUTHORITY_SEED: &[u8] = b"authority";
+
+#[constant]
+pub const COLLECTION_SEED: &[u8] = b"collection";
+
+#[constant]
+pub const PLANET_STATE_SEED: &[u8] = b"planet_state";
+
+#[constant]
+pub const GAME_SESSION_SEED: &[u8] = b"game_session";
+
+#[constant]
+pub const PLAYER_STATS_SEED: &[u8] = b"player_stats";
+
+#[constant]
+pub const GUESSES_LEADERBOARD_SEED: &[u8] = b"leaderboard_guesses";
+
+#[constant]
+pub const EJECTIONS_LEADERBOARD_SEED: &[u8] = b"leaderboard_ejections";
+
+#[constant]
+pub const BADGE_KIND_SEED: &[u8] = b"badge_kind";
+
+#[constant]
+pub const BADGE_SEED: &[u8] = b"badge";
+
+/// Planet ids are used as PDA seeds, which are limited to 32 bytes
+pub const MAX_PLANET_ID_LEN: usize = 32;
+
+/// Longest color descriptor stored in a `PlanetState`
+pub const MAX_COLOR_LEN: usize = 64;
+
+/// Planets (and researchers) in one game, as dealt by `generateRandomPlanets`
+pub const MAX_PLANETS_PER_GAME: usize = 10;
+
+/// Players kept on each leaderboard
+pub const LEADERBOARD_SIZE: usize = 100;
+
+/// Badge ids are used as PDA seeds, like planet ids
+pub const MAX_BADGE_ID_LEN: usize = 32;
+
+/// Metaplex limits for a badge's name and URI
+pub const MAX_BADGE_NAME_LEN: usize = 32;
+pub const MAX_BADGE_URI_LEN: usize = 200;
+
+/// Size of the SPL token payment allowlist on the config
+pub const MAX_PAYMENT_MINTS: usize = 8;
+
+/// Merkle trees `create_planet_tree` can register on the config
+pub const MAX_PLANET_TREES: usize = 8;
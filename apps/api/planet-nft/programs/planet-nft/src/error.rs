@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Invalid metadata account")]
+    InvalidMetadataAccount,
+    #[msg("Signer is not authorized to perform this action")]
+    Unauthorized,
+    #[msg("Legacy token account does not hold the planet NFT")]
+    NothingToMigrate,
+    #[msg("Mint attestation from the game server is missing or malformed")]
+    MissingAttestation,
+    #[msg("Mint attestation was not signed by the game server")]
+    InvalidAttestationSigner,
+    #[msg("Mint attestation has expired")]
+    AttestationExpired,
+    #[msg("Mint attestation does not match the instruction arguments")]
+    AttestationMismatch,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("There is no pending admin proposal")]
+    NoPendingAdmin,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("Player has already claimed this planet")]
+    PlanetAlreadyClaimed,
+    #[msg("Payer cannot cover the mint fee")]
+    InsufficientFundsForFee,
+    #[msg("Treasury cannot cover the withdrawal and stay rent exempt")]
+    InsufficientTreasuryBalance,
+    #[msg("Token is not accepted for paying the mint fee")]
+    PaymentMintNotAllowed,
+    #[msg("Payment allowlist is full")]
+    TooManyPaymentMints,
+    #[msg("Token payment needs the payment mint and both token accounts")]
+    IncompletePaymentAccounts,
+    #[msg("Royalty must be at most 10000 basis points")]
+    InvalidSellerFee,
+    #[msg("Discoverer share must be at most 100")]
+    InvalidDiscovererShare,
+    #[msg("Planet metadata was created immutable")]
+    MetadataImmutable,
+    #[msg("Player planet record belongs to a different mint")]
+    PlanetRecordMismatch,
+    #[msg("Recipient must sign to receive a soulbound planet")]
+    RecipientMustSign,
+    #[msg("Tree config account could not be read")]
+    InvalidTreeConfig,
+    #[msg("Temperature must be between 0 and 120°F")]
+    TemperatureOutOfRange,
+    #[msg("Ocean coverage must be between 0 and 95%")]
+    OceanCoverageOutOfRange,
+    #[msg("Gravity must be between 0.5g and 1.5g")]
+    GravityOutOfRange,
+    #[msg("Color must be between 1 and 64 bytes")]
+    InvalidColor,
+    #[msg("Planet name does not match the seed")]
+    PlanetNameMismatch,
+    #[msg("Temperature does not match the seed")]
+    TemperatureMismatch,
+    #[msg("Ocean coverage does not match the seed")]
+    OceanCoverageMismatch,
+    #[msg("Gravity does not match the seed")]
+    GravityMismatch,
+    #[msg("Color does not match the seed")]
+    ColorMismatch,
+    #[msg("Game session has already ended")]
+    SessionAlreadyEnded,
+    #[msg("A game session can only end as won or lost")]
+    InvalidOutcome,
+    #[msg("Game session was not won")]
+    GameNotWon,
+    #[msg("Reward for this game session has already been claimed")]
+    RewardAlreadyClaimed,
+    #[msg("Game session already has a commitment")]
+    AlreadyCommitted,
+    #[msg("Committed game sessions must be ended with reveal_game")]
+    SessionCommitted,
+    #[msg("Game session has no commitment to reveal")]
+    NotCommitted,
+    #[msg("Revealed game does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("A game must have between 1 and 10 planets")]
+    InvalidPlanetCount,
+    #[msg("Researcher index is out of range")]
+    ResearcherOutOfRange,
+    #[msg("Game session has not ended yet")]
+    SessionNotEnded,
+    #[msg("Result of this game session has already been recorded")]
+    ResultAlreadyRecorded,
+    #[msg("Player stats counter overflowed")]
+    StatsOverflow,
+    #[msg("Badge id, name or URI is too long, or the threshold is 0")]
+    InvalidBadgeKind,
+    #[msg("Player has not reached this badge's milestone")]
+    BadgeMilestoneNotReached,
+    #[msg("Signer does not hold the planet")]
+    NotPlanetHolder,
+    #[msg("Planet has no editions left to print")]
+    PrintLimitReached,
+    #[msg("Counter overflowed")]
+    Overflow,
+    #[msg("Merkle tree was not created by create_planet_tree")]
+    UnknownPlanetTree,
+    #[msg("Config already holds the maximum number of planet trees")]
+    TooManyPlanetTrees,
+    #[msg("Planet seed is not the planet selected in the game session")]
+    PlanetNotSelected,
+    #[msg("Game can only be committed in the slot its session started in")]
+    CommitTooLate,
+    #[msg("Wins must be ended by reveal_game against a commitment")]
+    WinNotRevealed,
+}
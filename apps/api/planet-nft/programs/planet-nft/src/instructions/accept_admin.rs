@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.pending_admin.is_some() @ ErrorCode::NoPendingAdmin,
+        constraint = config.pending_admin == Some(pending_admin.key()) @ ErrorCode::NotPendingAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub pending_admin: Signer<'info>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        let config = &mut self.config;
+        config.admin = self.pending_admin.key();
+        config.pending_admin = None;
+
+        msg!("Admin handed over to {}", config.admin);
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{burn_nft, BurnNft},
+    token::{burn, close_account, Burn, CloseAccount, Mint, Token, TokenAccount},
+};
+use mpl_token_metadata::ID as METADATA_PROGRAM_ID;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{PlanetState, PlayerPlanet};
+
+#[derive(Accounts)]
+pub struct BurnPlanet<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    // Closed so the planet can be claimed again. The rent goes to whoever
+    // burns the planet, even if it was first claimed by someone else.
+    // Legacy and migrated planets have no record.
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            PLAYER_PLANET_SEED,
+            player_planet.player.as_ref(),
+            player_planet.planet_id.as_bytes(),
+        ],
+        bump = player_planet.bump,
+        constraint = player_planet.mint == mint.key() @ ErrorCode::PlanetRecordMismatch,
+    )]
+    pub player_planet: Option<Account<'info, PlayerPlanet>>,
+
+    // Planets minted before attributes were stored on-chain don't have one
+    #[account(
+        mut,
+        close = owner,
+        seeds = [PLANET_STATE_SEED, mint.key().as_ref()],
+        bump = planet_state.bump,
+    )]
+    pub planet_state: Option<Account<'info, PlanetState>>,
+
+    /// CHECK: Metadata PDA, checked and closed by Metaplex
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition PDA, checked and closed by Metaplex. Empty for
+    /// planets from the first deployment, which never had one.
+    #[account(
+        mut,
+        seeds = [b"metadata", METADATA_PROGRAM_ID.as_ref(), mint.key().as_ref(), b"edition"],
+        bump,
+        seeds::program = METADATA_PROGRAM_ID,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Metadata of the planets collection, whose size Metaplex
+    /// decrements
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Token Metadata Program
+    #[account(address = METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> BurnPlanet<'info> {
+    pub fn burn_planet(&mut self) -> Result<()> {
+        match &self.player_planet {
+            Some(player_planet) => msg!(
+                "Burning planet {} ({})",
+                player_planet.planet_id,
+                self.mint.key()
+            ),
+            None => msg!("Burning planet {}", self.mint.key()),
+        }
+
+        if self.master_edition.data_is_empty() {
+            return self.burn_legacy_planet();
+        }
+
+        // Burns the token and closes the token account, metadata and edition,
+        // refunding their rent to the owner
+        burn_nft(
+            CpiContext::new(
+                self.token_metadata_program.to_account_info(),
+                BurnNft {
+                    metadata: self.metadata.to_account_info(),
+                    owner: self.owner.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    token: self.token_account.to_account_info(),
+                    edition: self.master_edition.to_account_info(),
+                    spl_token: self.token_program.to_account_info(),
+                },
+            )
+            .with_remaining_accounts(vec![self.collection_metadata.to_account_info()]),
+            Some(self.collection_metadata.key()),
+        )?;
+
+        Ok(())
+    }
+
+    /// Metaplex only burns NFTs with a master edition, so legacy planets are
+    /// burned through the token program. Their metadata was created
+    /// immutable and stays behind.
+    fn burn_legacy_planet(&self) -> Result<()> {
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.mint.to_account_info(),
+                    from: self.token_account.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        close_account(CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.token_account.to_account_info(),
+                destination: self.owner.to_account_info(),
+                authority: self.owner.to_account_info(),
+            },
+        ))?;
+
+        Ok(())
+    }
+}
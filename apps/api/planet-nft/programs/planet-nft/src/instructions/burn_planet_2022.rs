@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        mint_close_authority::MintCloseAuthority, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint as MintState,
+};
+use anchor_spl::token_interface::{
+    burn, close_account, Burn, CloseAccount, Mint, Token2022, TokenAccount,
+};
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{PlanetState, PlayerPlanet};
+
+#[derive(Accounts)]
+pub struct BurnPlanet2022<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Closed so the planet can be claimed again, as in `burn_planet`
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            PLAYER_PLANET_SEED,
+            player_planet.player.as_ref(),
+            player_planet.planet_id.as_bytes(),
+        ],
+        bump = player_planet.bump,
+        constraint = player_planet.mint == mint.key() @ ErrorCode::PlanetRecordMismatch,
+    )]
+    pub player_planet: Account<'info, PlayerPlanet>,
+
+    // Every Token-2022 planet has one, and it holds the planet_id for the
+    // mint authority seeds
+    #[account(
+        mut,
+        close = owner,
+        seeds = [PLANET_STATE_SEED, mint.key().as_ref()],
+        bump = planet_state.bump,
+    )]
+    pub planet_state: Box<Account<'info, PlanetState>>,
+
+    /// CHECK: Mint authority PDA, the mint's close authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, planet_state.planet_id.as_bytes()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+impl<'info> BurnPlanet2022<'info> {
+    pub fn burn_planet_2022(&mut self, bumps: &BurnPlanet2022Bumps) -> Result<()> {
+        msg!(
+            "Burning planet {} ({})",
+            self.planet_state.planet_id,
+            self.mint.key()
+        );
+
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.mint.to_account_info(),
+                    from: self.token_account.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        close_account(CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.token_account.to_account_info(),
+                destination: self.owner.to_account_info(),
+                authority: self.owner.to_account_info(),
+            },
+        ))?;
+
+        // Planets minted before the mint had a close authority keep their
+        // empty mint
+        if !self.has_close_authority()? {
+            return Ok(());
+        }
+
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            self.planet_state.planet_id.as_bytes(),
+            &[bumps.mint_authority],
+        ];
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.mint.to_account_info(),
+                destination: self.owner.to_account_info(),
+                authority: self.mint_authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        ))?;
+
+        Ok(())
+    }
+
+    fn has_close_authority(&self) -> Result<bool> {
+        let mint_info = self.mint.to_account_info();
+        let data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+        Ok(mint
+            .get_extension::<MintCloseAuthority>()
+            .is_ok_and(|extension| {
+                Option::<Pubkey>::from(extension.close_authority) == Some(self.mint_authority.key())
+            }))
+    }
+}
@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3, freeze_delegated_account,
+        mpl_token_metadata::types::{Creator, DataV2},
+        CreateMasterEditionV3, CreateMetadataAccountsV3, FreezeDelegatedAccount,
+    },
+    token::{approve, mint_to, Approve, Mint, MintTo, Token, TokenAccount},
+};
+use mpl_token_metadata::ID as METADATA_PROGRAM_ID;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{BadgeKind, Config, PlayerStats};
+
+#[derive(Accounts)]
+#[instruction(badge_id: String)]
+pub struct ClaimBadge<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [BADGE_KIND_SEED, badge_id.as_bytes()],
+        bump = badge_kind.bump,
+    )]
+    pub badge_kind: Account<'info, BadgeKind>,
+
+    #[account(
+        seeds = [PLAYER_STATS_SEED, player.key().as_ref()],
+        bump = player_stats.bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    // Signs to approve the freeze that makes the badge non-transferable
+    pub player: Signer<'info>,
+
+    // One badge of each kind per player
+    #[account(
+        init,
+        payer = payer,
+        seeds = [BADGE_SEED, badge_id.as_bytes(), player.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = program_authority,
+        mint::freeze_authority = program_authority,
+    )]
+    pub badge_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = badge_mint,
+        associated_token::authority = player,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metadata PDA of the badge, checked by Metaplex
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition PDA of the badge, checked by Metaplex
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Mint, update and freeze authority of every badge
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub program_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Token Metadata Program
+    #[account(address = METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimBadge<'info> {
+    pub fn claim_badge(&mut self, badge_id: String, bumps: &ClaimBadgeBumps) -> Result<()> {
+        let badge_kind = &self.badge_kind;
+        require!(
+            self.player_stats.get(badge_kind.stat) >= badge_kind.threshold,
+            ErrorCode::BadgeMilestoneNotReached
+        );
+        msg!("Claiming badge {} for {}", badge_id, self.player.key());
+
+        let authority_seeds = &[AUTHORITY_SEED, &[bumps.program_authority]];
+        let signer = &[&authority_seeds[..]];
+        let program_authority = self.program_authority.to_account_info();
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.badge_mint.to_account_info(),
+                    to: self.token_account.to_account_info(),
+                    authority: program_authority.clone(),
+                },
+                signer,
+            ),
+            1,
+        )?;
+
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: self.metadata.to_account_info(),
+                    mint: self.badge_mint.to_account_info(),
+                    mint_authority: program_authority.clone(),
+                    update_authority: program_authority.clone(),
+                    payer: self.payer.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                signer,
+            ),
+            DataV2 {
+                name: badge_kind.name.clone(),
+                symbol: "BADGE".to_string(),
+                uri: badge_kind.uri.clone(),
+                seller_fee_basis_points: 0,
+                // The program PDA signs as update authority, so it can be a
+                // verified creator straight away
+                creators: Some(vec![Creator {
+                    address: self.program_authority.key(),
+                    verified: true,
+                    share: 100,
+                }]),
+                collection: None,
+                uses: None,
+            },
+            false, // is_mutable
+            true,  // update_authority_is_signer
+            None,  // collection_details
+        )?;
+
+        create_master_edition_v3(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                CreateMasterEditionV3 {
+                    edition: self.master_edition.to_account_info(),
+                    mint: self.badge_mint.to_account_info(),
+                    update_authority: program_authority.clone(),
+                    mint_authority: program_authority.clone(),
+                    payer: self.payer.to_account_info(),
+                    metadata: self.metadata.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                signer,
+            ),
+            Some(0), // max_supply
+        )?;
+
+        // Frozen the same way as soulbound planets: the player approves the
+        // program PDA, which then freezes the account through Metaplex
+        approve(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Approve {
+                    to: self.token_account.to_account_info(),
+                    delegate: program_authority.clone(),
+                    authority: self.player.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        freeze_delegated_account(CpiContext::new_with_signer(
+            self.token_metadata_program.to_account_info(),
+            FreezeDelegatedAccount {
+                metadata: self.metadata.to_account_info(),
+                delegate: program_authority,
+                token_account: self.token_account.to_account_info(),
+                edition: self.master_edition.to_account_info(),
+                mint: self.badge_mint.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        self.badge_kind.claimed = self
+            .badge_kind
+            .claimed
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
+        msg!("Badge claimed: {}", self.badge_mint.key());
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, GameOutcome, GameSession};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CommitGame<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = game_server.key() == config.attestation_signer @ ErrorCode::Unauthorized
+    )]
+    pub game_server: Signer<'info>,
+
+    /// CHECK: Wallet of the player the game belongs to
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SESSION_SEED, player.key().as_ref(), &game_id.to_le_bytes()],
+        bump = game_session.bump,
+        constraint = game_session.outcome == GameOutcome::InProgress
+            @ ErrorCode::SessionAlreadyEnded,
+        constraint = game_session.commitment == [0; 32] @ ErrorCode::AlreadyCommitted,
+    )]
+    pub game_session: Account<'info, GameSession>,
+}
+
+impl<'info> CommitGame<'info> {
+    pub fn commit_game(&mut self, game_id: u64, commitment: [u8; 32]) -> Result<()> {
+        // A commitment made once play is under way could be picked to fit
+        // the player's guesses, so it has to land in the slot the session
+        // started in, i.e. in the same transaction as `start_session`
+        let slot = Clock::get()?.slot;
+        require!(
+            slot <= self.game_session.start_slot,
+            ErrorCode::CommitTooLate
+        );
+
+        self.game_session.commitment = commitment;
+        self.game_session.commit_slot = slot;
+
+        msg!("Game {} committed", game_id);
+        Ok(())
+    }
+}
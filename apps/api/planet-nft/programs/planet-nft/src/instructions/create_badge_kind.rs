@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{BadgeKind, BadgeStat, Config};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateBadgeKindArgs {
+    pub badge_id: String,
+    pub name: String,
+    pub uri: String,
+    pub stat: BadgeStat,
+    pub threshold: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateBadgeKindArgs)]
+pub struct CreateBadgeKind<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + BadgeKind::INIT_SPACE,
+        seeds = [BADGE_KIND_SEED, args.badge_id.as_bytes()],
+        bump,
+    )]
+    pub badge_kind: Account<'info, BadgeKind>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateBadgeKind<'info> {
+    pub fn create_badge_kind(
+        &mut self,
+        args: CreateBadgeKindArgs,
+        bumps: &CreateBadgeKindBumps,
+    ) -> Result<()> {
+        require!(
+            args.badge_id.len() <= MAX_BADGE_ID_LEN
+                && args.name.len() <= MAX_BADGE_NAME_LEN
+                && args.uri.len() <= MAX_BADGE_URI_LEN
+                && args.threshold > 0,
+            ErrorCode::InvalidBadgeKind
+        );
+
+        let badge_kind = &mut self.badge_kind;
+        badge_kind.badge_id = args.badge_id;
+        badge_kind.name = args.name;
+        badge_kind.uri = args.uri;
+        badge_kind.stat = args.stat;
+        badge_kind.threshold = args.threshold;
+        badge_kind.claimed = 0;
+        badge_kind.bump = bumps.badge_kind;
+
+        msg!("Badge kind created: {}", badge_kind.badge_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3,
+        mpl_token_metadata::types::{CollectionDetails, DataV2},
+        CreateMasterEditionV3, CreateMetadataAccountsV3,
+    },
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
+use mpl_token_metadata::ID as METADATA_PROGRAM_ID;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct CreateCollection<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Update authority of the collection, signs the membership
+    /// verification of every planet
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub program_authority: UncheckedAccount<'info>,
+
+    // A PDA so there can only ever be one planets collection
+    #[account(
+        init,
+        payer = admin,
+        seeds = [COLLECTION_SEED],
+        bump,
+        mint::decimals = 0,
+        mint::authority = program_authority,
+        mint::freeze_authority = program_authority,
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = collection_mint,
+        associated_token::authority = program_authority,
+    )]
+    pub collection_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Collection metadata PDA, checked by Metaplex
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition PDA, checked by Metaplex
+    #[account(mut)]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Token Metadata Program
+    #[account(address = METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> CreateCollection<'info> {
+    pub fn create_collection(
+        &mut self,
+        name: String,
+        uri: String,
+        bumps: &CreateCollectionBumps,
+    ) -> Result<()> {
+        let seeds = &[AUTHORITY_SEED, &[bumps.program_authority]];
+        let signer = &[&seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.collection_mint.to_account_info(),
+                    to: self.collection_token_account.to_account_info(),
+                    authority: self.program_authority.to_account_info(),
+                },
+                signer,
+            ),
+            1,
+        )?;
+
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: self.collection_metadata.to_account_info(),
+                    mint: self.collection_mint.to_account_info(),
+                    mint_authority: self.program_authority.to_account_info(),
+                    update_authority: self.program_authority.to_account_info(),
+                    payer: self.admin.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                signer,
+            ),
+            DataV2 {
+                name,
+                symbol: "PLANET".to_string(),
+                uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true, // is_mutable
+            true, // update_authority_is_signer
+            // Sized, so Metaplex keeps count of verified planets
+            Some(CollectionDetails::V1 { size: 0 }),
+        )?;
+
+        create_master_edition_v3(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                CreateMasterEditionV3 {
+                    edition: self.collection_master_edition.to_account_info(),
+                    mint: self.collection_mint.to_account_info(),
+                    update_authority: self.program_authority.to_account_info(),
+                    mint_authority: self.program_authority.to_account_info(),
+                    payer: self.admin.to_account_info(),
+                    metadata: self.collection_metadata.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                signer,
+            ),
+            Some(0),
+        )?;
+
+        msg!("Planets collection created: {}", self.collection_mint.key());
+        Ok(())
+    }
+}
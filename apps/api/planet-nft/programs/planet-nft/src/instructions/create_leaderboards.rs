@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, Leaderboard, LeaderboardKind};
+
+#[derive(Accounts)]
+pub struct CreateLeaderboards<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [GUESSES_LEADERBOARD_SEED],
+        bump,
+    )]
+    pub guesses_leaderboard: Box<Account<'info, Leaderboard>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [EJECTIONS_LEADERBOARD_SEED],
+        bump,
+    )]
+    pub ejections_leaderboard: Box<Account<'info, Leaderboard>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateLeaderboards<'info> {
+    pub fn create_leaderboards(&mut self, bumps: &CreateLeaderboardsBumps) -> Result<()> {
+        let guesses = &mut self.guesses_leaderboard;
+        guesses.kind = LeaderboardKind::CorrectGuesses;
+        guesses.season = 1;
+        guesses.entries = Vec::new();
+        guesses.bump = bumps.guesses_leaderboard;
+
+        let ejections = &mut self.ejections_leaderboard;
+        ejections.kind = LeaderboardKind::CorrectEjections;
+        ejections.season = 1;
+        ejections.entries = Vec::new();
+        ejections.bump = bumps.ejections_leaderboard;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::{
+    instructions::{
+        CreateTreeConfigCpi, CreateTreeConfigCpiAccounts, CreateTreeConfigInstructionArgs,
+    },
+    programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID},
+    ID as BUBBLEGUM_PROGRAM_ID,
+};
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct CreatePlanetTree<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+        constraint = config.planet_trees.len() < MAX_PLANET_TREES @ ErrorCode::TooManyPlanetTrees,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Becomes the tree creator, so only this program can mint into
+    /// the tree unless a delegate is set
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub program_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree config PDA, created by Bubblegum
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Empty merkle tree account, allocated and owned by the account
+    /// compression program earlier in the same transaction
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop program
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreatePlanetTree<'info> {
+    pub fn create_planet_tree(
+        &mut self,
+        max_depth: u32,
+        max_buffer_size: u32,
+        bumps: &CreatePlanetTreeBumps,
+    ) -> Result<()> {
+        let bubblegum_program = self.bubblegum_program.to_account_info();
+        let tree_config = self.tree_config.to_account_info();
+        let merkle_tree = self.merkle_tree.to_account_info();
+        let payer = self.admin.to_account_info();
+        let tree_creator = self.program_authority.to_account_info();
+        let log_wrapper = self.log_wrapper.to_account_info();
+        let compression_program = self.compression_program.to_account_info();
+        let system_program = self.system_program.to_account_info();
+
+        CreateTreeConfigCpi::new(
+            &bubblegum_program,
+            CreateTreeConfigCpiAccounts {
+                tree_config: &tree_config,
+                merkle_tree: &merkle_tree,
+                payer: &payer,
+                tree_creator: &tree_creator,
+                log_wrapper: &log_wrapper,
+                compression_program: &compression_program,
+                system_program: &system_program,
+            },
+            CreateTreeConfigInstructionArgs {
+                max_depth,
+                max_buffer_size,
+                // Private, so nobody else can mint planets into it
+                public: Some(false),
+            },
+        )
+        .invoke_signed(&[&[AUTHORITY_SEED, &[bumps.program_authority]]])?;
+
+        // Bubblegum would let the program mint into any tree that is public
+        // or delegated to it, so remember which trees are ours
+        self.config.planet_trees.push(self.merkle_tree.key());
+
+        msg!(
+            "Planet tree created: {} (depth {}, buffer {})",
+            self.merkle_tree.key(),
+            max_depth,
+            max_buffer_size
+        );
+        Ok(())
+    }
+}
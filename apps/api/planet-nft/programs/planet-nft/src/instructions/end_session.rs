@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, GameOutcome, GameSession};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EndSessionArgs {
+    pub game_id: u64,
+    /// Must be `Lost`; wins are ended by `reveal_game`
+    pub outcome: GameOutcome,
+    pub selected_researcher: u8,
+    pub selected_planet: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: EndSessionArgs)]
+pub struct EndSession<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = game_server.key() == config.attestation_signer @ ErrorCode::Unauthorized
+    )]
+    pub game_server: Signer<'info>,
+
+    /// CHECK: Wallet of the player the game belongs to
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SESSION_SEED, player.key().as_ref(), &args.game_id.to_le_bytes()],
+        bump = game_session.bump,
+        constraint = game_session.outcome == GameOutcome::InProgress
+            @ ErrorCode::SessionAlreadyEnded,
+        // Committed games have to be ended by `reveal_game`
+        constraint = game_session.commitment == [0; 32] @ ErrorCode::SessionCommitted,
+    )]
+    pub game_session: Account<'info, GameSession>,
+}
+
+impl<'info> EndSession<'info> {
+    pub fn end_session(&mut self, args: EndSessionArgs) -> Result<()> {
+        require!(
+            args.outcome != GameOutcome::InProgress,
+            ErrorCode::InvalidOutcome
+        );
+        // Only a revealed commitment can make a game a win, so every session
+        // that pays out was fixed before the player guessed
+        require!(args.outcome == GameOutcome::Lost, ErrorCode::WinNotRevealed);
+
+        let game_session = &mut self.game_session;
+        game_session.end_slot = Clock::get()?.slot;
+        game_session.outcome = args.outcome;
+        game_session.selected_researcher = args.selected_researcher;
+        game_session.selected_planet = args.selected_planet;
+
+        msg!("Game {} ended for {}", args.game_id, game_session.player);
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::program::PlanetNft;
+use crate::state::{Config, Treasury};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [TREASURY_SEED],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, PlanetNft>,
+
+    pub program_data: Account<'info, ProgramData>,
+
+    // Only the upgrade authority can create the config, so nobody can
+    // front-run the deployment and make themselves admin
+    #[account(
+        mut,
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(
+        &mut self,
+        admin: Pubkey,
+        attestation_signer: Pubkey,
+        bumps: &InitializeConfigBumps,
+    ) -> Result<()> {
+        let config = &mut self.config;
+        config.admin = admin;
+        config.pending_admin = None;
+        config.attestation_signer = attestation_signer;
+        config.paused = false;
+        config.mint_fee_lamports = 0;
+        config.payment_mints = Vec::new();
+        config.studio = admin;
+        config.seller_fee_basis_points = 0;
+        config.discoverer_share = 0;
+        config.max_prints = 0;
+        config.print_fee_lamports = 0;
+        config.planet_trees = Vec::new();
+        config.bump = bumps.config;
+
+        self.treasury.bump = bumps.treasury;
+
+        msg!("Config initialized with admin {}", admin);
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::program::PlanetNft;
+
+#[derive(Accounts)]
+#[instruction(planet_id: String)]
+pub struct MigratePlanetToOwner<'info> {
+    #[account(
+        seeds = [LEGACY_MINT_SEED, planet_id.as_bytes()],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Mint authority PDA - owns the legacy token account
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, planet_id.as_bytes()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = mint_authority,
+        constraint = legacy_token_account.amount == 1 @ ErrorCode::NothingToMigrate,
+    )]
+    pub legacy_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Rightful owner of the planet, vouched for by `authority`.
+    /// Receives the legacy account's rent.
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, PlanetNft>,
+
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> MigratePlanetToOwner<'info> {
+    pub fn migrate_planet_to_owner(
+        &mut self,
+        planet_id: String,
+        bumps: &MigratePlanetToOwnerBumps,
+    ) -> Result<()> {
+        msg!("Migrating Planet NFT {} to {}", planet_id, self.owner.key());
+
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            planet_id.as_bytes(),
+            &[bumps.mint_authority],
+        ];
+        let signer = &[&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.legacy_token_account.to_account_info(),
+                    to: self.owner_token_account.to_account_info(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                signer,
+            ),
+            1,
+        )?;
+
+        // Close the emptied legacy account and refund its rent to the owner,
+        // who paid for it when the planet was minted
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.legacy_token_account.to_account_info(),
+                destination: self.owner.to_account_info(),
+                authority: self.mint_authority.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        msg!("Planet NFT migrated successfully!");
+        Ok(())
+    }
+}
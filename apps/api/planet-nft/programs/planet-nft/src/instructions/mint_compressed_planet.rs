@@ -0,0 +1,312 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use mpl_bubblegum::{
+    accounts::TreeConfig,
+    instructions::{
+        MintToCollectionV1Cpi, MintToCollectionV1CpiAccounts, MintToCollectionV1InstructionArgs,
+    },
+    programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID},
+    types::{Collection, Creator, MetadataArgs, TokenProgramVersion, TokenStandard},
+    utils::get_asset_id,
+    ID as BUBBLEGUM_PROGRAM_ID,
+};
+use mpl_token_metadata::ID as METADATA_PROGRAM_ID;
+
+use crate::attestation::MintAttestation;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::instructions::mint_planet_nft::{claim_planet, pay_mint_fee, TokenPayment};
+use crate::state::{Config, GameOutcome, GameSession, PlanetStats, PlayerPlanet, Treasury};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintCompressedPlanetArgs {
+    pub planet_id: String,
+    /// `planet-generator` seed of the planet. The name and stats must be
+    /// the ones generated from it.
+    pub seed: u64,
+    pub planet_name: String,
+    pub metadata_uri: String,
+    pub game_id: u64,
+    pub expiry: i64,
+    pub nonce: u64,
+    /// Generated attributes. Only checked against the seed, since a
+    /// compressed planet has no account to keep them in.
+    pub stats: PlanetStats,
+}
+
+#[derive(Accounts)]
+#[instruction(args: MintCompressedPlanetArgs)]
+pub struct MintCompressedPlanet<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerPlanet::INIT_SPACE,
+        seeds = [
+            PLAYER_PLANET_SEED,
+            recipient.key().as_ref(),
+            args.planet_id.as_bytes(),
+        ],
+        bump,
+        constraint = player_planet.mint == Pubkey::default() @ ErrorCode::PlanetAlreadyClaimed,
+    )]
+    pub player_planet: Account<'info, PlayerPlanet>,
+
+    // The game the planet was won in; each won game pays out one planet,
+    // the one the player picked
+    #[account(
+        mut,
+        seeds = [GAME_SESSION_SEED, recipient.key().as_ref(), &args.game_id.to_le_bytes()],
+        bump = game_session.bump,
+        constraint = game_session.outcome == GameOutcome::Won @ ErrorCode::GameNotWon,
+        constraint = !game_session.reward_claimed @ ErrorCode::RewardAlreadyClaimed,
+        constraint = game_session.selected_planet == args.seed @ ErrorCode::PlanetNotSelected,
+    )]
+    pub game_session: Account<'info, GameSession>,
+
+    /// CHECK: Tree creator and collection update authority
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub program_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree config PDA, read for the leaf index
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Merkle tree created by `create_planet_tree`, checked against
+    /// the trees registered on the config
+    #[account(
+        mut,
+        constraint = config.planet_trees.contains(&merkle_tree.key()) @ ErrorCode::UnknownPlanetTree,
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet that owns the compressed planet
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(seeds = [COLLECTION_SEED], bump)]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Collection metadata PDA, checked by Metaplex
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition PDA, checked by Metaplex
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum's `["collection_cpi"]` PDA, checked by Bubblegum
+    pub bubblegum_signer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to find the game server's ed25519 attestation
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    // Optional SPL token payment of the mint fee, as in `mint_planet_nft`.
+    // There's no mint to create, so the token program is only needed for
+    // the payment.
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = payer,
+    )]
+    pub payer_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = treasury,
+    )]
+    pub treasury_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub payment_token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: SPL Noop program
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Token Metadata Program
+    #[account(address = METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MintCompressedPlanet<'info> {
+    pub fn mint_compressed_planet(
+        &mut self,
+        args: MintCompressedPlanetArgs,
+        bumps: &MintCompressedPlanetBumps,
+    ) -> Result<()> {
+        let MintCompressedPlanetArgs {
+            planet_id,
+            seed,
+            planet_name,
+            metadata_uri,
+            game_id,
+            expiry,
+            nonce,
+            stats,
+        } = args;
+        msg!(
+            "Minting compressed Planet NFT: {} ({})",
+            planet_name,
+            planet_id
+        );
+
+        stats.validate()?;
+        stats.verify_seed(seed, &planet_name)?;
+
+        // The new leaf's index, which together with the tree identifies the
+        // asset
+        let leaf_index = TreeConfig::from_bytes(&self.tree_config.try_borrow_data()?)
+            .map_err(|_| ErrorCode::InvalidTreeConfig)?
+            .num_minted;
+        let asset_id = get_asset_id(&self.merkle_tree.key(), leaf_index);
+
+        // Compressed planets can't be soulbound. There's no mint, so the
+        // `PlayerPlanet` record stores the asset id instead.
+        let attestation = MintAttestation {
+            planet_id,
+            recipient: self.recipient.key(),
+            game_id,
+            seed,
+            metadata_uri: metadata_uri.clone(),
+            expiry,
+            nonce,
+            soulbound: false,
+        };
+        claim_planet(
+            &self.config,
+            &self.instructions,
+            attestation,
+            &mut self.player_planet,
+            bumps.player_planet,
+            &mut self.game_session,
+            asset_id,
+        )?;
+
+        pay_mint_fee(
+            &self.config,
+            &self.payer,
+            &self.treasury,
+            &self.system_program,
+            TokenPayment {
+                mint: self.payment_mint.as_deref(),
+                from: self.payer_payment_account.as_deref(),
+                to: self.treasury_payment_account.as_deref(),
+                token_program: self.payment_token_program.as_ref(),
+            },
+        )?;
+
+        // The program PDA signs as tree creator, so it can be a verified
+        // creator straight away
+        let program_creator = Creator {
+            address: self.program_authority.key(),
+            verified: true,
+            share: 0,
+        };
+        let royalty_creators = self
+            .config
+            .royalty_shares(&self.recipient.key())
+            .into_iter()
+            .map(|(address, share)| Creator {
+                address,
+                verified: false,
+                share,
+            });
+        let metadata = MetadataArgs {
+            name: planet_name,
+            symbol: "PLANET".to_string(),
+            uri: metadata_uri,
+            seller_fee_basis_points: self.config.seller_fee_basis_points,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: Some(Collection {
+                verified: false,
+                key: self.collection_mint.key(),
+            }),
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: std::iter::once(program_creator)
+                .chain(royalty_creators)
+                .collect(),
+        };
+
+        let bubblegum_program = self.bubblegum_program.to_account_info();
+        let tree_config = self.tree_config.to_account_info();
+        let recipient = self.recipient.to_account_info();
+        let merkle_tree = self.merkle_tree.to_account_info();
+        let payer = self.payer.to_account_info();
+        let program_authority = self.program_authority.to_account_info();
+        let collection_mint = self.collection_mint.to_account_info();
+        let collection_metadata = self.collection_metadata.to_account_info();
+        let collection_master_edition = self.collection_master_edition.to_account_info();
+        let bubblegum_signer = self.bubblegum_signer.to_account_info();
+        let log_wrapper = self.log_wrapper.to_account_info();
+        let compression_program = self.compression_program.to_account_info();
+        let token_metadata_program = self.token_metadata_program.to_account_info();
+        let system_program = self.system_program.to_account_info();
+
+        MintToCollectionV1Cpi::new(
+            &bubblegum_program,
+            MintToCollectionV1CpiAccounts {
+                tree_config: &tree_config,
+                leaf_owner: &recipient,
+                leaf_delegate: &recipient,
+                merkle_tree: &merkle_tree,
+                payer: &payer,
+                tree_creator_or_delegate: &program_authority,
+                collection_authority: &program_authority,
+                collection_authority_record_pda: None,
+                collection_mint: &collection_mint,
+                collection_metadata: &collection_metadata,
+                collection_edition: &collection_master_edition,
+                bubblegum_signer: &bubblegum_signer,
+                log_wrapper: &log_wrapper,
+                compression_program: &compression_program,
+                token_metadata_program: &token_metadata_program,
+                system_program: &system_program,
+            },
+            MintToCollectionV1InstructionArgs { metadata },
+        )
+        .invoke_signed(&[&[AUTHORITY_SEED, &[bumps.program_authority]]])?;
+
+        msg!("Compressed planet asset id: {}", asset_id);
+
+        msg!("Planet NFT minted successfully!");
+        Ok(())
+    }
+}
@@ -0,0 +1,568 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3, freeze_delegated_account,
+        mpl_token_metadata::types::{Collection, Creator, DataV2},
+        sign_metadata, verify_sized_collection_item, CreateMasterEditionV3,
+        CreateMetadataAccountsV3, FreezeDelegatedAccount, SignMetadata, VerifySizedCollectionItem,
+    },
+    token::{
+        approve, mint_to, transfer_checked, Approve, Mint, MintTo, Token, TokenAccount,
+        TransferChecked,
+    },
+};
+use mpl_token_metadata::ID as METADATA_PROGRAM_ID;
+
+use crate::attestation::{verify_ed25519_attestation, MintAttestation};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{
+    Config, GameOutcome, GameSession, PlanetState, PlanetStats, PlayerPlanet, Treasury,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintPlanetArgs {
+    pub planet_id: String,
+    /// `planet-generator` seed of the planet. The name and stats must be
+    /// the ones generated from it.
+    pub seed: u64,
+    pub planet_name: String,
+    pub metadata_uri: String,
+    /// Game session the planet was won in (`game_sessions.id` in the backend)
+    pub game_id: u64,
+    /// Unix timestamp after which the attestation is no longer accepted
+    pub expiry: i64,
+    /// Makes each attestation unique; replays are already rejected because
+    /// a player can only hold one `PlayerPlanet` record per planet
+    pub nonce: u64,
+    /// Freeze the planet in the recipient's wallet so it can't be traded.
+    /// Part of the attestation, so only the game server decides.
+    pub soulbound: bool,
+    /// Generated attributes, stored in the planet's `PlanetState`
+    pub stats: PlanetStats,
+}
+
+#[derive(Accounts)]
+#[instruction(args: MintPlanetArgs)]
+pub struct MintPlanetNft<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    // Declared before `mint` so a duplicate claim fails here with
+    // `PlanetAlreadyClaimed` instead of somewhere less obvious
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerPlanet::INIT_SPACE,
+        seeds = [
+            PLAYER_PLANET_SEED,
+            recipient.key().as_ref(),
+            args.planet_id.as_bytes(),
+        ],
+        bump,
+        constraint = player_planet.mint == Pubkey::default() @ ErrorCode::PlanetAlreadyClaimed,
+    )]
+    pub player_planet: Account<'info, PlayerPlanet>,
+
+    // The game the planet was won in; each won game pays out one planet,
+    // the one the player picked
+    #[account(
+        mut,
+        seeds = [GAME_SESSION_SEED, recipient.key().as_ref(), &args.game_id.to_le_bytes()],
+        bump = game_session.bump,
+        constraint = game_session.outcome == GameOutcome::Won @ ErrorCode::GameNotWon,
+        constraint = !game_session.reward_claimed @ ErrorCode::RewardAlreadyClaimed,
+        constraint = game_session.selected_planet == args.seed @ ErrorCode::PlanetNotSelected,
+    )]
+    pub game_session: Account<'info, GameSession>,
+
+    // Each player gets their own copy of a planet, so the mint is a fresh
+    // keypair rather than a PDA of the planet_id
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PlanetState::INIT_SPACE,
+        seeds = [PLANET_STATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub planet_state: Box<Account<'info, PlanetState>>,
+
+    /// CHECK: Mint authority PDA - uses separate seeds from mint
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, args.planet_id.as_bytes()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet that receives the NFT (usually the payer). Only used as
+    /// the associated token account owner, and must sign for soulbound
+    /// planets.
+    pub recipient: UncheckedAccount<'info>,
+
+    // The mint is new, so its associated token account can't exist yet
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metadata account (PDA derived from mint by Metaplex)
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition PDA, checked by Metaplex
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Update authority of the planets collection
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub program_authority: UncheckedAccount<'info>,
+
+    #[account(seeds = [COLLECTION_SEED], bump)]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Collection metadata PDA, checked by Metaplex. Mutable because
+    /// verifying bumps the collection size.
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition PDA, checked by Metaplex
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Token Metadata Program
+    #[account(address = METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to find the game server's ed25519 attestation
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    // Optional SPL token payment of the mint fee. When all three are given
+    // the token fee from the config's allowlist is charged instead of SOL.
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = payer,
+    )]
+    pub payer_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = treasury,
+    )]
+    pub treasury_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> MintPlanetNft<'info> {
+    pub fn mint_planet_nft(
+        &mut self,
+        args: MintPlanetArgs,
+        bumps: &MintPlanetNftBumps,
+    ) -> Result<()> {
+        let MintPlanetArgs {
+            planet_id,
+            seed,
+            planet_name,
+            metadata_uri,
+            game_id,
+            expiry,
+            nonce,
+            soulbound,
+            stats,
+        } = args;
+        msg!("Minting Planet NFT: {} ({})", planet_name, planet_id);
+        msg!("Metadata URI: {}", metadata_uri);
+        msg!("Recipient: {}", self.recipient.key());
+
+        stats.validate()?;
+        stats.verify_seed(seed, &planet_name)?;
+
+        let attestation = MintAttestation {
+            planet_id: planet_id.clone(),
+            recipient: self.recipient.key(),
+            game_id,
+            seed,
+            metadata_uri: metadata_uri.clone(),
+            expiry,
+            nonce,
+            soulbound,
+        };
+        claim_planet(
+            &self.config,
+            &self.instructions,
+            attestation,
+            &mut self.player_planet,
+            bumps.player_planet,
+            &mut self.game_session,
+            self.mint.key(),
+        )?;
+
+        pay_mint_fee(
+            &self.config,
+            &self.payer,
+            &self.treasury,
+            &self.system_program,
+            TokenPayment {
+                mint: self.payment_mint.as_deref(),
+                from: self.payer_payment_account.as_deref(),
+                to: self.treasury_payment_account.as_deref(),
+                token_program: Some(&self.token_program),
+            },
+        )?;
+
+        // Mint 1 token to the recipient's associated token account
+        // Use mint_authority PDA seeds for signing
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            planet_id.as_bytes(),
+            &[bumps.mint_authority],
+        ];
+        let signer = &[&seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.token_account.to_account_info(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                signer,
+            ),
+            1, // Amount: 1 for NFT
+        )?;
+
+        // Verify metadata PDA is correct
+        let mint_key = self.mint.key();
+        let metadata_seeds = &[b"metadata", METADATA_PROGRAM_ID.as_ref(), mint_key.as_ref()];
+        let (expected_metadata_pda, _metadata_bump) =
+            Pubkey::find_program_address(metadata_seeds, &METADATA_PROGRAM_ID);
+        require!(
+            self.metadata.key() == expected_metadata_pda,
+            ErrorCode::InvalidMetadataAccount
+        );
+
+        // Create metadata account (derived PDA)
+        let metadata_account_info = &mut self.metadata.to_account_info();
+        let mint_account_info = &self.mint.to_account_info();
+        let mint_authority_info = &self.mint_authority.to_account_info();
+        let payer_info = &self.payer.to_account_info();
+        let token_metadata_program_info = &self.token_metadata_program.to_account_info();
+        let system_program_info = &self.system_program.to_account_info();
+        let rent_info = &self.rent.to_account_info();
+
+        let metadata_data_v2 = DataV2 {
+            name: planet_name.clone(),
+            symbol: "PLANET".to_string(),
+            uri: metadata_uri.clone(),
+            seller_fee_basis_points: self.config.seller_fee_basis_points,
+            creators: Some(self.creators()),
+            collection: Some(Collection {
+                verified: false,
+                key: self.collection_mint.key(),
+            }),
+            uses: None,
+        };
+
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                token_metadata_program_info.clone(),
+                CreateMetadataAccountsV3 {
+                    metadata: metadata_account_info.clone(),
+                    mint: mint_account_info.clone(),
+                    mint_authority: mint_authority_info.clone(),
+                    update_authority: mint_authority_info.clone(),
+                    payer: payer_info.clone(),
+                    system_program: system_program_info.clone(),
+                    rent: rent_info.clone(),
+                },
+                signer,
+            ),
+            metadata_data_v2,
+            true, // is_mutable, so update_planet_metadata can fix URIs
+            true, // update_authority_is_signer
+            None, // collection_details
+        )?;
+
+        // Hands mint and freeze authority to the edition account, so the
+        // supply stays at 1 for good. The discoverer can still print up to
+        // `max_prints` numbered editions with `print_planet_edition`.
+        create_master_edition_v3(
+            CpiContext::new_with_signer(
+                token_metadata_program_info.clone(),
+                CreateMasterEditionV3 {
+                    edition: self.master_edition.to_account_info(),
+                    mint: mint_account_info.clone(),
+                    update_authority: mint_authority_info.clone(),
+                    mint_authority: mint_authority_info.clone(),
+                    payer: payer_info.clone(),
+                    metadata: metadata_account_info.clone(),
+                    token_program: self.token_program.to_account_info(),
+                    system_program: system_program_info.clone(),
+                    rent: rent_info.clone(),
+                },
+                signer,
+            ),
+            Some(self.config.max_prints), // max_supply
+        )?;
+
+        if soulbound {
+            self.freeze_token_account(signer)?;
+        }
+
+        let authority_seeds = &[AUTHORITY_SEED, &[bumps.program_authority]];
+
+        // The program PDA is the first creator. Marking it verified proves
+        // the planet was minted here rather than copied by someone else.
+        sign_metadata(CpiContext::new_with_signer(
+            token_metadata_program_info.clone(),
+            SignMetadata {
+                creator: self.program_authority.to_account_info(),
+                metadata: metadata_account_info.clone(),
+            },
+            &[&authority_seeds[..]],
+        ))?;
+
+        // Only the collection's update authority can flip `verified`, so
+        // marketplaces can trust that the planet came from this program
+        verify_sized_collection_item(
+            CpiContext::new_with_signer(
+                token_metadata_program_info.clone(),
+                VerifySizedCollectionItem {
+                    payer: payer_info.clone(),
+                    metadata: metadata_account_info.clone(),
+                    collection_authority: self.program_authority.to_account_info(),
+                    collection_mint: self.collection_mint.to_account_info(),
+                    collection_metadata: self.collection_metadata.to_account_info(),
+                    collection_master_edition: self.collection_master_edition.to_account_info(),
+                },
+                &[&authority_seeds[..]],
+            ),
+            None, // collection_authority_record
+        )?;
+
+        let planet_state = &mut self.planet_state;
+        planet_state.mint = self.mint.key();
+        planet_state.planet_id = planet_id;
+        planet_state.seed = seed;
+        planet_state.stats = stats;
+        planet_state.bump = bumps.planet_state;
+
+        msg!("Planet NFT minted successfully!");
+        Ok(())
+    }
+
+    /// Program PDA with no share, followed by the royalty shares
+    fn creators(&self) -> Vec<Creator> {
+        let program_creator = Creator {
+            address: self.program_authority.key(),
+            verified: false,
+            share: 0,
+        };
+        let royalty_creators = self
+            .config
+            .royalty_shares(&self.recipient.key())
+            .into_iter()
+            .map(|(address, share)| Creator {
+                address,
+                verified: false,
+                share,
+            });
+        std::iter::once(program_creator)
+            .chain(royalty_creators)
+            .collect()
+    }
+
+    /// Freezes the recipient's token account. Once the master edition exists
+    /// only Metaplex can freeze it, and only on behalf of a delegate, so the
+    /// recipient first approves the `mint_authority` PDA.
+    fn freeze_token_account(&self, signer: &[&[&[u8]]]) -> Result<()> {
+        require!(self.recipient.is_signer, ErrorCode::RecipientMustSign);
+
+        approve(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Approve {
+                    to: self.token_account.to_account_info(),
+                    delegate: self.mint_authority.to_account_info(),
+                    authority: self.recipient.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        freeze_delegated_account(CpiContext::new_with_signer(
+            self.token_metadata_program.to_account_info(),
+            FreezeDelegatedAccount {
+                metadata: self.metadata.to_account_info(),
+                delegate: self.mint_authority.to_account_info(),
+                token_account: self.token_account.to_account_info(),
+                edition: self.master_edition.to_account_info(),
+                mint: self.mint.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        msg!("Planet is soulbound");
+        Ok(())
+    }
+}
+
+/// Checks and records shared by the three mint instructions. The game
+/// server's attestation, signed in an ed25519 instruction placed right before
+/// the mint, must be unexpired and match the mint and the game session. Then
+/// the claim is recorded
+/// in the recipient's `PlayerPlanet`, and the won game session is marked as
+/// paid out. `mint` is the asset id for compressed planets.
+pub(crate) fn claim_planet<'info>(
+    config: &Config,
+    instructions: &UncheckedAccount<'info>,
+    attestation: MintAttestation,
+    player_planet: &mut Account<'info, PlayerPlanet>,
+    player_planet_bump: u8,
+    game_session: &mut Account<'info, GameSession>,
+    mint: Pubkey,
+) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp <= attestation.expiry,
+        ErrorCode::AttestationExpired
+    );
+    require!(
+        attestation.game_id == game_session.game_id
+            && attestation.seed == game_session.selected_planet,
+        ErrorCode::AttestationMismatch
+    );
+    verify_ed25519_attestation(
+        &instructions.to_account_info(),
+        &config.attestation_signer,
+        &attestation.try_to_vec()?,
+    )?;
+
+    player_planet.player = attestation.recipient;
+    player_planet.planet_id = attestation.planet_id;
+    player_planet.mint = mint;
+    player_planet.earned_slot = Clock::get()?.slot;
+    player_planet.game_id = game_session.game_id;
+    player_planet.bump = player_planet_bump;
+
+    game_session.reward_claimed = true;
+    Ok(())
+}
+
+/// Optional accounts for paying the mint fee in an allowlisted SPL token
+pub(crate) struct TokenPayment<'a, 'info> {
+    pub mint: Option<&'a Account<'info, Mint>>,
+    pub from: Option<&'a Account<'info, TokenAccount>>,
+    pub to: Option<&'a Account<'info, TokenAccount>>,
+    pub token_program: Option<&'a Program<'info, Token>>,
+}
+
+/// Charges the token fee from the config's allowlist when all the payment
+/// accounts are given, and the SOL fee when none are
+pub(crate) fn pay_mint_fee<'info>(
+    config: &Config,
+    payer: &Signer<'info>,
+    treasury: &Account<'info, Treasury>,
+    system_program: &Program<'info, System>,
+    token_payment: TokenPayment<'_, 'info>,
+) -> Result<()> {
+    match token_payment {
+        TokenPayment {
+            mint: Some(payment_mint),
+            from: Some(from),
+            to: Some(to),
+            token_program: Some(token_program),
+        } => {
+            let amount = config
+                .payment_mint(&payment_mint.key())
+                .ok_or(ErrorCode::PaymentMintNotAllowed)?
+                .amount;
+            transfer_checked(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: payment_mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: payer.to_account_info(),
+                    },
+                ),
+                amount,
+                payment_mint.decimals,
+            )?;
+            msg!("Mint fee paid: {} of {}", amount, payment_mint.key());
+        }
+        TokenPayment {
+            mint: None,
+            from: None,
+            to: None,
+            ..
+        } => pay_sol_mint_fee(config, payer, treasury, system_program)?,
+        _ => return err!(ErrorCode::IncompletePaymentAccounts),
+    }
+    Ok(())
+}
+
+/// Moves `config.mint_fee_lamports` from the payer to the treasury
+fn pay_sol_mint_fee<'info>(
+    config: &Config,
+    payer: &Signer<'info>,
+    treasury: &Account<'info, Treasury>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let fee = config.mint_fee_lamports;
+    if fee > 0 {
+        // Check up front so a short payer gets a clear error instead of a
+        // failed system transfer
+        require!(payer.lamports() >= fee, ErrorCode::InsufficientFundsForFee);
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+        msg!("Mint fee paid: {} lamports", fee);
+    }
+    Ok(())
+}
@@ -0,0 +1,366 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Token},
+    token_2022::spl_token_2022::instruction::AuthorityType,
+    token_interface::{
+        freeze_account, mint_to, set_authority,
+        spl_token_metadata_interface::state::{Field, TokenMetadata},
+        token_metadata_initialize, token_metadata_update_field, FreezeAccount, Mint, MintTo,
+        SetAuthority, Token2022, TokenAccount, TokenMetadataInitialize, TokenMetadataUpdateField,
+    },
+};
+
+use crate::attestation::MintAttestation;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::instructions::mint_planet_nft::{claim_planet, pay_mint_fee, TokenPayment};
+use crate::state::{
+    Config, GameOutcome, GameSession, PlanetState, PlanetStats, PlayerPlanet, Treasury,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintPlanet2022Args {
+    pub planet_id: String,
+    /// `planet-generator` seed of the planet. The name and stats must be
+    /// the ones generated from it.
+    pub seed: u64,
+    pub planet_name: String,
+    pub metadata_uri: String,
+    pub game_id: u64,
+    pub expiry: i64,
+    pub nonce: u64,
+    /// Freeze the planet in the recipient's wallet, as in `mint_planet_nft`
+    pub soulbound: bool,
+    /// Generated attributes, stored in the planet's `PlanetState` and as
+    /// additional fields in the mint's token metadata
+    pub stats: PlanetStats,
+}
+
+#[derive(Accounts)]
+#[instruction(args: MintPlanet2022Args)]
+pub struct MintPlanetNft2022<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerPlanet::INIT_SPACE,
+        seeds = [
+            PLAYER_PLANET_SEED,
+            recipient.key().as_ref(),
+            args.planet_id.as_bytes(),
+        ],
+        bump,
+        constraint = player_planet.mint == Pubkey::default() @ ErrorCode::PlanetAlreadyClaimed,
+    )]
+    pub player_planet: Account<'info, PlayerPlanet>,
+
+    // The game the planet was won in; each won game pays out one planet,
+    // the one the player picked
+    #[account(
+        mut,
+        seeds = [GAME_SESSION_SEED, recipient.key().as_ref(), &args.game_id.to_le_bytes()],
+        bump = game_session.bump,
+        constraint = game_session.outcome == GameOutcome::Won @ ErrorCode::GameNotWon,
+        constraint = !game_session.reward_claimed @ ErrorCode::RewardAlreadyClaimed,
+        constraint = game_session.selected_planet == args.seed @ ErrorCode::PlanetNotSelected,
+    )]
+    pub game_session: Account<'info, GameSession>,
+
+    // The metadata lives in the mint itself, so the pointer points back at
+    // it. The close authority lets `burn_planet_2022` close the mint.
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = mint_authority,
+        extensions::metadata_pointer::metadata_address = mint,
+        extensions::close_authority::authority = mint_authority,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PlanetState::INIT_SPACE,
+        seeds = [PLANET_STATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub planet_state: Box<Account<'info, PlanetState>>,
+
+    /// CHECK: Mint authority PDA, also the token metadata update authority
+    /// and, for soulbound planets, the freeze authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, args.planet_id.as_bytes()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet that receives the NFT. Only used as the associated token
+    /// account owner.
+    pub recipient: UncheckedAccount<'info>,
+
+    // The mint is new, so its associated token account can't exist yet
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to find the game server's ed25519 attestation
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    // Optional SPL token payment of the mint fee, as in `mint_planet_nft`.
+    // The fee tokens belong to the original token program, so it's passed
+    // alongside Token-2022.
+    pub payment_mint: Option<Box<Account<'info, token::Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = payer,
+    )]
+    pub payer_payment_account: Option<Box<Account<'info, token::TokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = treasury,
+    )]
+    pub treasury_payment_account: Option<Box<Account<'info, token::TokenAccount>>>,
+
+    pub payment_token_program: Option<Program<'info, Token>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> MintPlanetNft2022<'info> {
+    pub fn mint_planet_nft_2022(
+        &mut self,
+        args: MintPlanet2022Args,
+        bumps: &MintPlanetNft2022Bumps,
+    ) -> Result<()> {
+        let MintPlanet2022Args {
+            planet_id,
+            seed,
+            planet_name,
+            metadata_uri,
+            game_id,
+            expiry,
+            nonce,
+            soulbound,
+            stats,
+        } = args;
+        msg!(
+            "Minting Token-2022 Planet NFT: {} ({})",
+            planet_name,
+            planet_id
+        );
+
+        stats.validate()?;
+        stats.verify_seed(seed, &planet_name)?;
+
+        let attestation = MintAttestation {
+            planet_id: planet_id.clone(),
+            recipient: self.recipient.key(),
+            game_id,
+            seed,
+            metadata_uri: metadata_uri.clone(),
+            expiry,
+            nonce,
+            soulbound,
+        };
+        claim_planet(
+            &self.config,
+            &self.instructions,
+            attestation,
+            &mut self.player_planet,
+            bumps.player_planet,
+            &mut self.game_session,
+            self.mint.key(),
+        )?;
+
+        pay_mint_fee(
+            &self.config,
+            &self.payer,
+            &self.treasury,
+            &self.system_program,
+            TokenPayment {
+                mint: self.payment_mint.as_deref(),
+                from: self.payer_payment_account.as_deref(),
+                to: self.treasury_payment_account.as_deref(),
+                token_program: self.payment_token_program.as_ref(),
+            },
+        )?;
+
+        let fields = metadata_fields(&stats);
+        let symbol = "PLANET".to_string();
+
+        // Token-2022 grows the mint for the metadata but doesn't pay for it,
+        // so top up the rent for the full metadata up front
+        let metadata = TokenMetadata {
+            name: planet_name.clone(),
+            symbol: symbol.clone(),
+            uri: metadata_uri.clone(),
+            additional_metadata: fields
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+            ..Default::default()
+        };
+        let mint_info = self.mint.to_account_info();
+        let required = Rent::get()?.minimum_balance(mint_info.data_len() + metadata.tlv_size_of()?);
+        let top_up = required.saturating_sub(mint_info.lamports());
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: mint_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            planet_id.as_bytes(),
+            &[bumps.mint_authority],
+        ];
+        let signer = &[&seeds[..]];
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    metadata: mint_info.clone(),
+                    update_authority: self.mint_authority.to_account_info(),
+                    mint_authority: self.mint_authority.to_account_info(),
+                    mint: mint_info.clone(),
+                },
+                signer,
+            ),
+            planet_name,
+            symbol,
+            metadata_uri,
+        )?;
+
+        for (key, value) in fields {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TokenMetadataUpdateField {
+                        token_program_id: self.token_program.to_account_info(),
+                        metadata: mint_info.clone(),
+                        update_authority: self.mint_authority.to_account_info(),
+                    },
+                    signer,
+                ),
+                Field::Key(key.to_string()),
+                value,
+            )?;
+        }
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: mint_info.clone(),
+                    to: self.token_account.to_account_info(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                signer,
+            ),
+            1,
+        )?;
+
+        // Token-2022 lets the freeze authority freeze directly, so unlike
+        // `mint_planet_nft` the recipient doesn't need to sign. The admin
+        // thaws it with `thaw_planet_2022`.
+        if soulbound {
+            freeze_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                FreezeAccount {
+                    account: self.token_account.to_account_info(),
+                    mint: mint_info.clone(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                signer,
+            ))?;
+            msg!("Planet is soulbound");
+        }
+
+        // There's no master edition here, so drop the mint authority to keep
+        // the supply at 1
+        set_authority(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: self.mint_authority.to_account_info(),
+                    account_or_mint: mint_info,
+                },
+                signer,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        let planet_state = &mut self.planet_state;
+        planet_state.mint = self.mint.key();
+        planet_state.planet_id = planet_id;
+        planet_state.seed = seed;
+        planet_state.stats = stats;
+        planet_state.bump = bumps.planet_state;
+
+        msg!("Planet NFT minted successfully!");
+        Ok(())
+    }
+}
+
+/// The stats in the same display format the game uses (e.g. "72°F", "45%",
+/// "1.12g")
+fn metadata_fields(stats: &PlanetStats) -> [(&'static str, String); 4] {
+    [
+        ("temperature", format!("{}°F", stats.temperature_f)),
+        ("ocean_coverage", format!("{}%", stats.ocean_coverage)),
+        (
+            "gravity",
+            format!(
+                "{}.{:02}g",
+                stats.gravity_centi_g / 100,
+                stats.gravity_centi_g % 100
+            ),
+        ),
+        ("color", stats.color.clone()),
+    ]
+}
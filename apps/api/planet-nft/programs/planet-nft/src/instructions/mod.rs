@@ -0,0 +1,55 @@
+pub mod accept_admin;
+pub mod burn_planet;
+pub mod burn_planet_2022;
+pub mod claim_badge;
+pub mod commit_game;
+pub mod create_badge_kind;
+pub mod create_collection;
+pub mod create_leaderboards;
+pub mod create_planet_tree;
+pub mod end_session;
+pub mod initialize_config;
+pub mod migrate_planet_to_owner;
+pub mod mint_compressed_planet;
+pub mod mint_planet_nft;
+pub mod mint_planet_nft_2022;
+pub mod print_planet_edition;
+pub mod record_game_result;
+pub mod reset_leaderboards;
+pub mod reveal_game;
+pub mod set_planet_tree_delegate;
+pub mod start_session;
+pub mod thaw_planet;
+pub mod thaw_planet_2022;
+pub mod update_config;
+pub mod update_planet_metadata;
+pub mod withdraw_treasury;
+pub mod withdraw_treasury_tokens;
+
+pub use accept_admin::*;
+pub use burn_planet::*;
+pub use burn_planet_2022::*;
+pub use claim_badge::*;
+pub use commit_game::*;
+pub use create_badge_kind::*;
+pub use create_collection::*;
+pub use create_leaderboards::*;
+pub use create_planet_tree::*;
+pub use end_session::*;
+pub use initialize_config::*;
+pub use migrate_planet_to_owner::*;
+pub use mint_compressed_planet::*;
+pub use mint_planet_nft::*;
+pub use mint_planet_nft_2022::*;
+pub use print_planet_edition::*;
+pub use record_game_result::*;
+pub use reset_leaderboards::*;
+pub use reveal_game::*;
+pub use set_planet_tree_delegate::*;
+pub use start_session::*;
+pub use thaw_planet::*;
+pub use thaw_planet_2022::*;
+pub use update_config::*;
+pub use update_planet_metadata::*;
+pub use withdraw_treasury::*;
+pub use withdraw_treasury_tokens::*;
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        mint_new_edition_from_master_edition_via_token, MasterEditionAccount,
+        MintNewEditionFromMasterEditionViaToken,
+    },
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
+use mpl_token_metadata::ID as METADATA_PROGRAM_ID;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, PlayerPlanet};
+
+#[derive(Accounts)]
+#[instruction(planet_id: String)]
+pub struct PrintPlanetEdition<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    // The discoverer's claim on the planet, which ties the master mint to
+    // the wallet that gets the print fee
+    #[account(
+        seeds = [PLAYER_PLANET_SEED, discoverer.key().as_ref(), planet_id.as_bytes()],
+        bump = player_planet.bump,
+        constraint = player_planet.mint == master_mint.key() @ ErrorCode::PlanetRecordMismatch,
+    )]
+    pub player_planet: Account<'info, PlayerPlanet>,
+
+    /// CHECK: Player who discovered the planet; receives the print fee
+    #[account(mut)]
+    pub discoverer: UncheckedAccount<'info>,
+
+    /// Current holder of the planet, who signs for the print
+    pub holder: Signer<'info>,
+
+    #[account(
+        token::mint = master_mint,
+        token::authority = holder,
+        constraint = holder_token_account.amount == 1 @ ErrorCode::NotPlanetHolder,
+    )]
+    pub holder_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub master_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Metadata PDA of the planet, checked by Metaplex
+    pub master_metadata: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = master_edition.max_supply.is_some_and(|max| master_edition.supply < max)
+            @ ErrorCode::PrintLimitReached,
+    )]
+    pub master_edition: Box<Account<'info, MasterEditionAccount>>,
+
+    /// CHECK: Update authority of the planet's metadata, copied to the print
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, planet_id.as_bytes()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet that receives the print
+    pub recipient: UncheckedAccount<'info>,
+
+    // Metaplex moves mint and freeze authority to the print's edition
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = program_authority,
+        mint::freeze_authority = program_authority,
+    )]
+    pub new_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = new_mint,
+        associated_token::authority = recipient,
+    )]
+    pub new_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metadata PDA of the print, created by Metaplex
+    #[account(mut)]
+    pub new_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Edition PDA of the print, created by Metaplex
+    #[account(mut)]
+    pub new_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex edition marker for the print's number, checked by
+    /// Metaplex
+    #[account(mut)]
+    pub edition_mark_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Mint authority of new prints
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub program_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Metaplex Token Metadata Program
+    #[account(address = METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> PrintPlanetEdition<'info> {
+    pub fn print_planet_edition(
+        &mut self,
+        planet_id: String,
+        bumps: &PrintPlanetEditionBumps,
+    ) -> Result<()> {
+        let edition = self.master_edition.supply + 1;
+        msg!("Printing edition {} of {}", edition, planet_id);
+
+        // Nothing to pay when the discoverer prints for themselves
+        let fee = self.config.print_fee_lamports;
+        if fee > 0 && self.payer.key() != self.discoverer.key() {
+            require!(
+                self.payer.lamports() >= fee,
+                ErrorCode::InsufficientFundsForFee
+            );
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: self.discoverer.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+            msg!("Print fee paid: {} lamports", fee);
+        }
+
+        let authority_seeds = &[AUTHORITY_SEED, &[bumps.program_authority]];
+        let signer = &[&authority_seeds[..]];
+
+        // Metaplex only prints onto a mint that already holds its one token
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.new_mint.to_account_info(),
+                    to: self.new_token_account.to_account_info(),
+                    authority: self.program_authority.to_account_info(),
+                },
+                signer,
+            ),
+            1,
+        )?;
+
+        mint_new_edition_from_master_edition_via_token(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                MintNewEditionFromMasterEditionViaToken {
+                    new_metadata: self.new_metadata.to_account_info(),
+                    new_edition: self.new_edition.to_account_info(),
+                    master_edition: self.master_edition.to_account_info(),
+                    new_mint: self.new_mint.to_account_info(),
+                    edition_mark_pda: self.edition_mark_pda.to_account_info(),
+                    new_mint_authority: self.program_authority.to_account_info(),
+                    payer: self.payer.to_account_info(),
+                    token_account_owner: self.holder.to_account_info(),
+                    token_account: self.holder_token_account.to_account_info(),
+                    new_metadata_update_authority: self.mint_authority.to_account_info(),
+                    metadata: self.master_metadata.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                    metadata_mint: self.master_mint.to_account_info(),
+                },
+                signer,
+            ),
+            edition,
+        )?;
+
+        msg!("Printed edition {}: {}", edition, self.new_mint.key());
+        Ok(())
+    }
+}
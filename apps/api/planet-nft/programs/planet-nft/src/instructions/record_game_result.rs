@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+use crate::attestation::{verify_ed25519_attestation, GameResultAttestation};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, GameOutcome, GameSession, Leaderboard, PlayerStats};
+
+/// Counts from one game, added to the player's totals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RecordGameResultArgs {
+    pub game_id: u64,
+    pub correct_guesses: u32,
+    pub correct_ejections: u32,
+    pub incorrect_guesses: u32,
+    pub incorrect_ejections: u32,
+}
+
+#[derive(Accounts)]
+#[instruction(args: RecordGameResultArgs)]
+pub struct RecordGameResult<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Wallet of the player the game belongs to
+    pub player: UncheckedAccount<'info>,
+
+    // Each finished game counts once
+    #[account(
+        mut,
+        seeds = [GAME_SESSION_SEED, player.key().as_ref(), &args.game_id.to_le_bytes()],
+        bump = game_session.bump,
+        constraint = game_session.outcome != GameOutcome::InProgress
+            @ ErrorCode::SessionNotEnded,
+        constraint = !game_session.result_recorded @ ErrorCode::ResultAlreadyRecorded,
+    )]
+    pub game_session: Account<'info, GameSession>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [PLAYER_STATS_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    #[account(
+        mut,
+        seeds = [GUESSES_LEADERBOARD_SEED],
+        bump = guesses_leaderboard.bump,
+    )]
+    pub guesses_leaderboard: Box<Account<'info, Leaderboard>>,
+
+    #[account(
+        mut,
+        seeds = [EJECTIONS_LEADERBOARD_SEED],
+        bump = ejections_leaderboard.bump,
+    )]
+    pub ejections_leaderboard: Box<Account<'info, Leaderboard>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to find the game server's ed25519 attestation
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordGameResult<'info> {
+    pub fn record_game_result(
+        &mut self,
+        args: RecordGameResultArgs,
+        bumps: &RecordGameResultBumps,
+    ) -> Result<()> {
+        // Anyone can submit the result, but only the game server can sign it
+        let result = GameResultAttestation {
+            player: self.player.key(),
+            game_id: args.game_id,
+            correct_guesses: args.correct_guesses,
+            correct_ejections: args.correct_ejections,
+            incorrect_guesses: args.incorrect_guesses,
+            incorrect_ejections: args.incorrect_ejections,
+        };
+        verify_ed25519_attestation(
+            &self.instructions.to_account_info(),
+            &self.config.attestation_signer,
+            &result.try_to_vec()?,
+        )?;
+
+        let slot = Clock::get()?.slot;
+        let player_stats = &mut self.player_stats;
+        player_stats.player = self.player.key();
+        player_stats.bump = bumps.player_stats;
+        // Both boards are reset together, so they're always in the same season
+        player_stats.record(
+            &result,
+            self.game_session.outcome == GameOutcome::Won,
+            self.guesses_leaderboard.season,
+            slot,
+        )?;
+
+        self.guesses_leaderboard.submit(
+            player_stats.player,
+            player_stats.season_correct_guesses,
+            slot,
+        );
+        self.ejections_leaderboard.submit(
+            player_stats.player,
+            player_stats.season_correct_ejections,
+            slot,
+        );
+
+        self.game_session.result_recorded = true;
+
+        msg!("Recorded game {} for {}", args.game_id, self.player.key());
+        Ok(())
+    }
+}
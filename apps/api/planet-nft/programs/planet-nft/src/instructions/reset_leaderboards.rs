@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, Leaderboard};
+
+#[derive(Accounts)]
+pub struct ResetLeaderboards<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GUESSES_LEADERBOARD_SEED],
+        bump = guesses_leaderboard.bump,
+    )]
+    pub guesses_leaderboard: Box<Account<'info, Leaderboard>>,
+
+    #[account(
+        mut,
+        seeds = [EJECTIONS_LEADERBOARD_SEED],
+        bump = ejections_leaderboard.bump,
+    )]
+    pub ejections_leaderboard: Box<Account<'info, Leaderboard>>,
+}
+
+impl<'info> ResetLeaderboards<'info> {
+    pub fn reset_leaderboards(&mut self) -> Result<()> {
+        for leaderboard in [
+            &mut self.guesses_leaderboard,
+            &mut self.ejections_leaderboard,
+        ] {
+            leaderboard.entries.clear();
+            leaderboard.season = leaderboard
+                .season
+                .checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+
+        msg!(
+            "Leaderboards reset for season {}",
+            self.guesses_leaderboard.season
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, GameOutcome, GameSession};
+
+/// The secret committed by `commit_game`, plus the player's pick
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RevealGameArgs {
+    pub game_id: u64,
+    /// Index of the real researcher among the game's planets
+    pub researcher_index: u8,
+    /// `planet-generator` seeds of the game's planets, in the order they
+    /// were dealt
+    pub planet_seeds: Vec<u64>,
+    pub salt: [u8; 32],
+    /// Index of the researcher the player picked
+    pub selected_researcher: u8,
+}
+
+impl RevealGameArgs {
+    /// `sha256(researcher_index || planet_seeds as u64 LE || salt)`
+    pub fn commitment(&self) -> [u8; 32] {
+        let seeds: Vec<u8> = self
+            .planet_seeds
+            .iter()
+            .flat_map(|seed| seed.to_le_bytes())
+            .collect();
+        hashv(&[&[self.researcher_index], &seeds, &self.salt]).to_bytes()
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(args: RevealGameArgs)]
+pub struct RevealGame<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Only the game server knows which researcher the player picked
+    #[account(
+        constraint = game_server.key() == config.attestation_signer @ ErrorCode::Unauthorized
+    )]
+    pub game_server: Signer<'info>,
+
+    /// CHECK: Wallet of the player the game belongs to
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SESSION_SEED, player.key().as_ref(), &args.game_id.to_le_bytes()],
+        bump = game_session.bump,
+        constraint = game_session.outcome == GameOutcome::InProgress
+            @ ErrorCode::SessionAlreadyEnded,
+        constraint = game_session.commitment != [0; 32] @ ErrorCode::NotCommitted,
+    )]
+    pub game_session: Account<'info, GameSession>,
+}
+
+impl<'info> RevealGame<'info> {
+    pub fn reveal_game(&mut self, args: RevealGameArgs) -> Result<()> {
+        let planets = args.planet_seeds.len();
+        require!(
+            (1..=MAX_PLANETS_PER_GAME).contains(&planets),
+            ErrorCode::InvalidPlanetCount
+        );
+        require!(
+            (args.researcher_index as usize) < planets
+                && (args.selected_researcher as usize) < planets,
+            ErrorCode::ResearcherOutOfRange
+        );
+        require!(
+            args.commitment() == self.game_session.commitment,
+            ErrorCode::CommitmentMismatch
+        );
+
+        // The outcome follows from the revealed secret, not from the server
+        let outcome = if args.selected_researcher == args.researcher_index {
+            GameOutcome::Won
+        } else {
+            GameOutcome::Lost
+        };
+
+        let game_session = &mut self.game_session;
+        game_session.end_slot = Clock::get()?.slot;
+        game_session.outcome = outcome;
+        game_session.selected_researcher = args.selected_researcher;
+        game_session.selected_planet = args.planet_seeds[args.selected_researcher as usize];
+        game_session.revealed = true;
+
+        msg!(
+            "Game {} revealed for {}: {}",
+            args.game_id,
+            game_session.player,
+            if outcome == GameOutcome::Won {
+                "won"
+            } else {
+                "lost"
+            }
+        );
+        Ok(())
+    }
+}
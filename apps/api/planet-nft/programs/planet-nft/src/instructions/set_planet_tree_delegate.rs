@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::{
+    instructions::{SetTreeDelegateCpi, SetTreeDelegateCpiAccounts},
+    ID as BUBBLEGUM_PROGRAM_ID,
+};
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetPlanetTreeDelegate<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: Tree creator PDA
+    #[account(seeds = [AUTHORITY_SEED], bump)]
+    pub program_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree config PDA
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = BUBBLEGUM_PROGRAM_ID,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Merkle tree created by `create_planet_tree`, checked against
+    /// the trees registered on the config
+    #[account(
+        constraint = config.planet_trees.contains(&merkle_tree.key()) @ ErrorCode::UnknownPlanetTree,
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Any key; gets to mint into the tree next to the program
+    pub new_tree_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetPlanetTreeDelegate<'info> {
+    pub fn set_planet_tree_delegate(&mut self, bumps: &SetPlanetTreeDelegateBumps) -> Result<()> {
+        let bubblegum_program = self.bubblegum_program.to_account_info();
+        let tree_config = self.tree_config.to_account_info();
+        let tree_creator = self.program_authority.to_account_info();
+        let new_tree_delegate = self.new_tree_delegate.to_account_info();
+        let merkle_tree = self.merkle_tree.to_account_info();
+        let system_program = self.system_program.to_account_info();
+
+        SetTreeDelegateCpi::new(
+            &bubblegum_program,
+            SetTreeDelegateCpiAccounts {
+                tree_config: &tree_config,
+                tree_creator: &tree_creator,
+                new_tree_delegate: &new_tree_delegate,
+                merkle_tree: &merkle_tree,
+                system_program: &system_program,
+            },
+        )
+        .invoke_signed(&[&[AUTHORITY_SEED, &[bumps.program_authority]]])?;
+
+        msg!(
+            "Delegate of tree {} set to {}",
+            self.merkle_tree.key(),
+            self.new_tree_delegate.key()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, GameOutcome, GameSession};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct StartSession<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Sessions are recorded by the game server, the same key that signs mint
+    // attestations
+    #[account(
+        constraint = game_server.key() == config.attestation_signer @ ErrorCode::Unauthorized
+    )]
+    pub game_server: Signer<'info>,
+
+    /// CHECK: Wallet of the player the game belongs to
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GameSession::INIT_SPACE,
+        seeds = [GAME_SESSION_SEED, player.key().as_ref(), &game_id.to_le_bytes()],
+        bump,
+    )]
+    pub game_session: Account<'info, GameSession>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StartSession<'info> {
+    pub fn start_session(&mut self, game_id: u64, bumps: &StartSessionBumps) -> Result<()> {
+        let game_session = &mut self.game_session;
+        game_session.player = self.player.key();
+        game_session.game_id = game_id;
+        game_session.start_slot = Clock::get()?.slot;
+        game_session.end_slot = 0;
+        game_session.outcome = GameOutcome::InProgress;
+        game_session.selected_researcher = 0;
+        game_session.selected_planet = 0;
+        game_session.commitment = [0; 32];
+        game_session.commit_slot = 0;
+        game_session.revealed = false;
+        game_session.result_recorded = false;
+        game_session.reward_claimed = false;
+        game_session.bump = bumps.game_session;
+
+        msg!("Game {} started for {}", game_id, game_session.player);
+        Ok(())
+    }
+}
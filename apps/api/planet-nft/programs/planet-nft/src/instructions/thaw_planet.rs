@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{thaw_delegated_account, ThawDelegatedAccount},
+    token::{Mint, Token, TokenAccount},
+};
+use mpl_token_metadata::ID as METADATA_PROGRAM_ID;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::Config;
+
+#[derive(Accounts)]
+#[instruction(planet_id: String)]
+pub struct ThawPlanet<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: Mint authority PDA, the delegate that froze the token account
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, planet_id.as_bytes()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metadata PDA of the planet
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition PDA, the mint's freeze authority. Checked by
+    /// Metaplex.
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Token Metadata Program
+    #[account(address = METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ThawPlanet<'info> {
+    pub fn thaw_planet(&mut self, planet_id: String, bumps: &ThawPlanetBumps) -> Result<()> {
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            planet_id.as_bytes(),
+            &[bumps.mint_authority],
+        ];
+
+        thaw_delegated_account(CpiContext::new_with_signer(
+            self.token_metadata_program.to_account_info(),
+            ThawDelegatedAccount {
+                metadata: self.metadata.to_account_info(),
+                delegate: self.mint_authority.to_account_info(),
+                token_account: self.token_account.to_account_info(),
+                edition: self.master_edition.to_account_info(),
+                mint: self.mint.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+            &[&seeds[..]],
+        ))?;
+
+        msg!(
+            "Thawed planet {} in {}",
+            planet_id,
+            self.token_account.key()
+        );
+        Ok(())
+    }
+}
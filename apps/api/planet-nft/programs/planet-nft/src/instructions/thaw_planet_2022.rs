@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{thaw_account, Mint, ThawAccount, Token2022, TokenAccount};
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::Config;
+
+#[derive(Accounts)]
+#[instruction(planet_id: String)]
+pub struct ThawPlanet2022<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: Mint authority PDA, the mint's freeze authority
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, planet_id.as_bytes()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+impl<'info> ThawPlanet2022<'info> {
+    pub fn thaw_planet_2022(
+        &mut self,
+        planet_id: String,
+        bumps: &ThawPlanet2022Bumps,
+    ) -> Result<()> {
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            planet_id.as_bytes(),
+            &[bumps.mint_authority],
+        ];
+
+        thaw_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            ThawAccount {
+                account: self.token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.mint_authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        ))?;
+
+        msg!(
+            "Thawed planet {} in {}",
+            planet_id,
+            self.token_account.key()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, PaymentMint};
+
+/// Fields left as `None` keep their current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateConfigArgs {
+    pub attestation_signer: Option<Pubkey>,
+    pub mint_fee_lamports: Option<u64>,
+    pub studio: Option<Pubkey>,
+    pub seller_fee_basis_points: Option<u16>,
+    pub discoverer_share: Option<u8>,
+    pub max_prints: Option<u64>,
+    pub print_fee_lamports: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(&mut self, args: UpdateConfigArgs) -> Result<()> {
+        let config = &mut self.config;
+
+        if let Some(attestation_signer) = args.attestation_signer {
+            msg!("Attestation signer set to {}", attestation_signer);
+            config.attestation_signer = attestation_signer;
+        }
+
+        if let Some(mint_fee_lamports) = args.mint_fee_lamports {
+            msg!("Mint fee set to {} lamports", mint_fee_lamports);
+            config.mint_fee_lamports = mint_fee_lamports;
+        }
+
+        if let Some(studio) = args.studio {
+            msg!("Studio set to {}", studio);
+            config.studio = studio;
+        }
+
+        if let Some(seller_fee_basis_points) = args.seller_fee_basis_points {
+            require!(
+                seller_fee_basis_points <= 10_000,
+                ErrorCode::InvalidSellerFee
+            );
+            msg!("Royalty set to {} basis points", seller_fee_basis_points);
+            config.seller_fee_basis_points = seller_fee_basis_points;
+        }
+
+        if let Some(discoverer_share) = args.discoverer_share {
+            require!(discoverer_share <= 100, ErrorCode::InvalidDiscovererShare);
+            msg!("Discoverer share set to {}%", discoverer_share);
+            config.discoverer_share = discoverer_share;
+        }
+
+        if let Some(max_prints) = args.max_prints {
+            msg!("Max prints set to {}", max_prints);
+            config.max_prints = max_prints;
+        }
+
+        if let Some(print_fee_lamports) = args.print_fee_lamports {
+            msg!("Print fee set to {} lamports", print_fee_lamports);
+            config.print_fee_lamports = print_fee_lamports;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        self.config.paused = paused;
+
+        msg!("Program {}", if paused { "paused" } else { "unpaused" });
+        Ok(())
+    }
+
+    /// Adds `mint` to the payment allowlist, or changes its amount if it's
+    /// already there
+    pub fn set_payment_mint(&mut self, mint: Pubkey, amount: u64) -> Result<()> {
+        let payment_mints = &mut self.config.payment_mints;
+
+        match payment_mints.iter_mut().find(|p| p.mint == mint) {
+            Some(payment_mint) => payment_mint.amount = amount,
+            None => {
+                require!(
+                    payment_mints.len() < MAX_PAYMENT_MINTS,
+                    ErrorCode::TooManyPaymentMints
+                );
+                payment_mints.push(PaymentMint { mint, amount });
+            }
+        }
+
+        msg!("Mint fee in {} set to {}", mint, amount);
+        Ok(())
+    }
+
+    pub fn remove_payment_mint(&mut self, mint: Pubkey) -> Result<()> {
+        let payment_mints = &mut self.config.payment_mints;
+        let len = payment_mints.len();
+        payment_mints.retain(|p| p.mint != mint);
+        require!(payment_mints.len() < len, ErrorCode::PaymentMintNotAllowed);
+
+        msg!("Removed {} from the payment mints", mint);
+        Ok(())
+    }
+
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.config.pending_admin = Some(new_admin);
+
+        msg!("Proposed {} as the new admin", new_admin);
+        Ok(())
+    }
+
+    pub fn cancel_admin_proposal(&mut self) -> Result<()> {
+        require!(
+            self.config.pending_admin.is_some(),
+            ErrorCode::NoPendingAdmin
+        );
+        self.config.pending_admin = None;
+
+        msg!("Admin proposal cancelled");
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{
+    mpl_token_metadata::types::DataV2, update_metadata_accounts_v2, MetadataAccount,
+    UpdateMetadataAccountsV2,
+};
+use mpl_token_metadata::ID as METADATA_PROGRAM_ID;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::Config;
+
+/// Only the URI can change. The name was checked against the planet's seed
+/// when it was minted, so it has to keep matching the `PlanetState`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdatePlanetMetadataArgs {
+    pub planet_id: String,
+    pub metadata_uri: String,
+}
+
+#[derive(Accounts)]
+#[instruction(args: UpdatePlanetMetadataArgs)]
+pub struct UpdatePlanetMetadata<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // The admin, or the game server once real metadata has been uploaded
+    #[account(
+        constraint = authority.key() == config.admin
+            || authority.key() == config.attestation_signer
+            @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Update authority of the planet's metadata
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, args.planet_id.as_bytes()],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = metadata.update_authority == mint_authority.key()
+            @ ErrorCode::InvalidMetadataAccount,
+        // Planets minted before metadata was mutable can't be changed
+        constraint = metadata.is_mutable @ ErrorCode::MetadataImmutable,
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>,
+
+    /// CHECK: Metaplex Token Metadata Program
+    #[account(address = METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+impl<'info> UpdatePlanetMetadata<'info> {
+    pub fn update_planet_metadata(
+        &mut self,
+        args: UpdatePlanetMetadataArgs,
+        bumps: &UpdatePlanetMetadataBumps,
+    ) -> Result<()> {
+        let UpdatePlanetMetadataArgs {
+            planet_id,
+            metadata_uri,
+        } = args;
+
+        // Metaplex pads stored strings with NULs
+        let current = &self.metadata;
+        let trim = |value: &str| value.trim_end_matches('\0').to_string();
+        let data = DataV2 {
+            name: trim(&current.name),
+            symbol: trim(&current.symbol),
+            uri: metadata_uri,
+            // Creators and collection are passed back unchanged so they
+            // stay verified
+            seller_fee_basis_points: current.seller_fee_basis_points,
+            creators: current.creators.clone(),
+            collection: current.collection.clone(),
+            uses: current.uses.clone(),
+        };
+        msg!(
+            "Updating metadata of {}: {} ({})",
+            planet_id,
+            data.name,
+            data.uri
+        );
+
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            planet_id.as_bytes(),
+            &[bumps.mint_authority],
+        ];
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                self.token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: self.metadata.to_account_info(),
+                    update_authority: self.mint_authority.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            None, // new_update_authority
+            Some(data),
+            None, // primary_sale_happened
+            None, // is_mutable
+        )?;
+
+        Ok(())
+    }
+}
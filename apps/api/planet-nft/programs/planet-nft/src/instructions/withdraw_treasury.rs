@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, Treasury};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: Any account can receive the withdrawn lamports
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+impl<'info> WithdrawTreasury<'info> {
+    pub fn withdraw_treasury(&mut self, amount: u64) -> Result<()> {
+        let treasury = self.treasury.to_account_info();
+
+        // The treasury is program-owned, so we move lamports directly, but
+        // never below its rent-exempt minimum
+        let rent_exempt = Rent::get()?.minimum_balance(treasury.data_len());
+        let available = treasury.lamports().saturating_sub(rent_exempt);
+        require!(amount <= available, ErrorCode::InsufficientTreasuryBalance);
+
+        treasury.sub_lamports(amount)?;
+        self.destination.add_lamports(amount)?;
+
+        msg!(
+            "Withdrew {} lamports from the treasury to {}",
+            amount,
+            self.destination.key()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::{Config, Treasury};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasuryTokens<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub admin: Signer<'info>,
+
+    pub payment_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = treasury,
+    )]
+    pub treasury_payment_account: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = payment_mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawTreasuryTokens<'info> {
+    pub fn withdraw_treasury_tokens(&mut self, amount: u64) -> Result<()> {
+        let seeds = &[TREASURY_SEED, &[self.treasury.bump]];
+        let signer = &[&seeds[..]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.treasury_payment_account.to_account_info(),
+                    mint: self.payment_mint.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.treasury.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+            self.payment_mint.decimals,
+        )?;
+
+        msg!(
+            "Withdrew {} of {} from the treasury",
+            amount,
+            self.payment_mint.key()
+        );
+        Ok(())
+    }
+}
@@ -1,11 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    metadata::{
-        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
-    },
-    token::{mint_to, Mint, MintTo, Token, TokenAccount},
-};
-use mpl_token_metadata::ID as METADATA_PROGRAM_ID;
+
+pub mod attestation;
+pub mod constants;
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+pub use constants::*;
+pub use instructions::*;
+pub use state::*;
 
 declare_id!("Fb7uNXapsRwUdsvGDedesLS7D1A4AHk6CeMvrrvTVqwf");
 
@@ -13,143 +16,211 @@ declare_id!("Fb7uNXapsRwUdsvGDedesLS7D1A4AHk6CeMvrrvTVqwf");
 pub mod planet_nft {
     use super::*;
 
-    pub fn mint_planet_nft(
-        ctx: Context<MintPlanetNft>,
-        planet_id: String,
-        planet_name: String,
-        metadata_uri: String,
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        admin: Pubkey,
+        attestation_signer: Pubkey,
     ) -> Result<()> {
-        msg!("Minting Planet NFT: {} ({})", planet_name, planet_id);
-        msg!("Metadata URI: {}", metadata_uri);
-
-        // Mint 1 token to the token account
-        // Use mint_authority PDA seeds for signing
-        let seeds = &[
-            b"mint_authority",
-            planet_id.as_bytes(),
-            &[ctx.bumps.mint_authority],
-        ];
-        let signer = &[&seeds[..]];
-
-        mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.token_account.to_account_info(),
-                    authority: ctx.accounts.mint_authority.to_account_info(),
-                },
-                signer,
-            ),
-            1, // Amount: 1 for NFT
-        )?;
-
-        // Verify metadata PDA is correct
-        let mint_key = ctx.accounts.mint.key();
-        let metadata_seeds = &[
-            b"metadata",
-            METADATA_PROGRAM_ID.as_ref(),
-            mint_key.as_ref(),
-        ];
-        let (expected_metadata_pda, _metadata_bump) = Pubkey::find_program_address(
-            metadata_seeds,
-            &METADATA_PROGRAM_ID,
-        );
-        require!(
-            ctx.accounts.metadata.key() == expected_metadata_pda,
-            ErrorCode::InvalidMetadataAccount
-        );
-
-        // Create metadata account (derived PDA)
-        let metadata_account_info = &mut ctx.accounts.metadata.to_account_info();
-        let mint_account_info = &ctx.accounts.mint.to_account_info();
-        let mint_authority_info = &ctx.accounts.mint_authority.to_account_info();
-        let payer_info = &ctx.accounts.payer.to_account_info();
-        let token_metadata_program_info = &ctx.accounts.token_metadata_program.to_account_info();
-        let system_program_info = &ctx.accounts.system_program.to_account_info();
-        let rent_info = &ctx.accounts.rent.to_account_info();
-
-        let creators = vec![];
-        let metadata_data_v2 = DataV2 {
-            name: planet_name.clone(),
-            symbol: "PLANET".to_string(),
-            uri: metadata_uri.clone(),
-            seller_fee_basis_points: 0,
-            creators: Some(creators),
-            collection: None,
-            uses: None,
-        };
-
-        create_metadata_accounts_v3(
-            CpiContext::new(
-                token_metadata_program_info.clone(),
-                CreateMetadataAccountsV3 {
-                    metadata: metadata_account_info.clone(),
-                    mint: mint_account_info.clone(),
-                    mint_authority: mint_authority_info.clone(),
-                    update_authority: mint_authority_info.clone(),
-                    payer: payer_info.clone(),
-                    system_program: system_program_info.clone(),
-                    rent: rent_info.clone(),
-                },
-            ),
-            metadata_data_v2,
-            false, // is_mutable
-            true,  // update_authority_is_signer
-            None,  // collection_details
-        )?;
-
-        msg!("Planet NFT minted successfully!");
-        Ok(())
+        ctx.accounts
+            .initialize_config(admin, attestation_signer, &ctx.bumps)
     }
-}
 
-#[derive(Accounts)]
-#[instruction(planet_id: String, planet_name: String, metadata_uri: String)]
-pub struct MintPlanetNft<'info> {
-    #[account(
-        init,
-        seeds = [b"planet_nft", planet_id.as_bytes()],
-        bump,
-        payer = payer,
-        mint::decimals = 0,
-        mint::authority = mint_authority,
-    )]
-    pub mint: Account<'info, Mint>,
-
-    /// CHECK: Mint authority PDA - uses separate seeds from mint
-    #[account(
-        seeds = [b"mint_authority", planet_id.as_bytes()],
-        bump
-    )]
-    pub mint_authority: UncheckedAccount<'info>,
-
-    #[account(
-        init,
-        payer = payer,
-        token::mint = mint,
-        token::authority = mint_authority,
-    )]
-    pub token_account: Account<'info, TokenAccount>,
-
-    /// CHECK: Metadata account (PDA derived from mint by Metaplex)
-    #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
-
-    /// CHECK: Metaplex Token Metadata Program
-    #[account(address = METADATA_PROGRAM_ID)]
-    pub token_metadata_program: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub payer: Signer<'info>,
-
-    pub rent: Sysvar<'info, Rent>,
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-}
+    pub fn update_config(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
+        ctx.accounts.update_config(args)
+    }
+
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        ctx.accounts.set_paused(paused)
+    }
+
+    /// First step of an admin handover. The proposal only takes effect once
+    /// the new admin signs `accept_admin`, so a mistyped key can't lock the
+    /// program out.
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
+    }
+
+    pub fn cancel_admin_proposal(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.cancel_admin_proposal()
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_treasury(amount)
+    }
+
+    pub fn withdraw_treasury_tokens(
+        ctx: Context<WithdrawTreasuryTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.withdraw_treasury_tokens(amount)
+    }
+
+    pub fn set_payment_mint(ctx: Context<UpdateConfig>, mint: Pubkey, amount: u64) -> Result<()> {
+        ctx.accounts.set_payment_mint(mint, amount)
+    }
+
+    pub fn remove_payment_mint(ctx: Context<UpdateConfig>, mint: Pubkey) -> Result<()> {
+        ctx.accounts.remove_payment_mint(mint)
+    }
+
+    /// One-off setup of the sized collection every planet is verified into
+    pub fn create_collection(
+        ctx: Context<CreateCollection>,
+        name: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts.create_collection(name, uri, &ctx.bumps)
+    }
+
+    /// One-off setup of the correct guesses and correct ejections
+    /// leaderboards
+    pub fn create_leaderboards(ctx: Context<CreateLeaderboards>) -> Result<()> {
+        ctx.accounts.create_leaderboards(&ctx.bumps)
+    }
+
+    /// Clears both leaderboards at the end of a season
+    pub fn reset_leaderboards(ctx: Context<ResetLeaderboards>) -> Result<()> {
+        ctx.accounts.reset_leaderboards()
+    }
+
+    /// Records the start of a game, signed by the game server
+    pub fn start_session(ctx: Context<StartSession>, game_id: u64) -> Result<()> {
+        ctx.accounts.start_session(game_id, &ctx.bumps)
+    }
+
+    /// Records the outcome of a game. Only a won session can be redeemed for
+    /// a planet, and only once.
+    pub fn end_session(ctx: Context<EndSession>, args: EndSessionArgs) -> Result<()> {
+        ctx.accounts.end_session(args)
+    }
+
+    /// Fixes the game's secret (which researcher is real, and the planets)
+    /// before play by storing its hash on the session
+    pub fn commit_game(ctx: Context<CommitGame>, game_id: u64, commitment: [u8; 32]) -> Result<()> {
+        ctx.accounts.commit_game(game_id, commitment)
+    }
+
+    /// Ends a committed game by revealing the secret. The outcome is worked
+    /// out from the revealed researcher and the player's pick, so anyone can
+    /// audit it.
+    pub fn reveal_game(ctx: Context<RevealGame>, args: RevealGameArgs) -> Result<()> {
+        ctx.accounts.reveal_game(args)
+    }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Invalid metadata account")]
-    InvalidMetadataAccount,
+    /// Adds a finished game to the player's `PlayerStats`. The counts must
+    /// be attested by the game server, and each game counts once.
+    pub fn record_game_result(
+        ctx: Context<RecordGameResult>,
+        args: RecordGameResultArgs,
+    ) -> Result<()> {
+        ctx.accounts.record_game_result(args, &ctx.bumps)
+    }
+
+    /// Defines a badge and the `PlayerStats` milestone that earns it
+    pub fn create_badge_kind(
+        ctx: Context<CreateBadgeKind>,
+        args: CreateBadgeKindArgs,
+    ) -> Result<()> {
+        ctx.accounts.create_badge_kind(args, &ctx.bumps)
+    }
+
+    /// Mints a non-transferable badge NFT to a player who reached the
+    /// badge's milestone. Each badge can be claimed once per player.
+    pub fn claim_badge(ctx: Context<ClaimBadge>, badge_id: String) -> Result<()> {
+        ctx.accounts.claim_badge(badge_id, &ctx.bumps)
+    }
+
+    /// Pays the SOL fee from `config.mint_fee_lamports`, or the token fee when
+    /// the optional payment accounts are passed.
+    pub fn mint_planet_nft(ctx: Context<MintPlanetNft>, args: MintPlanetArgs) -> Result<()> {
+        ctx.accounts.mint_planet_nft(args, &ctx.bumps)
+    }
+
+    /// Prints the next numbered edition of a planet for `recipient`, signed
+    /// by the planet's holder. The payer owes the discoverer
+    /// `config.print_fee_lamports`.
+    pub fn print_planet_edition(ctx: Context<PrintPlanetEdition>, planet_id: String) -> Result<()> {
+        ctx.accounts.print_planet_edition(planet_id, &ctx.bumps)
+    }
+
+    /// Swaps in a new URI, e.g. once the real metadata has been uploaded.
+    /// Signed by the admin or the game server.
+    pub fn update_planet_metadata(
+        ctx: Context<UpdatePlanetMetadata>,
+        args: UpdatePlanetMetadataArgs,
+    ) -> Result<()> {
+        ctx.accounts.update_planet_metadata(args, &ctx.bumps)
+    }
+
+    /// Mints the planet as a Token-2022 NFT whose name, URI and attributes
+    /// live in the mint's token metadata extension instead of Metaplex
+    pub fn mint_planet_nft_2022(
+        ctx: Context<MintPlanetNft2022>,
+        args: MintPlanet2022Args,
+    ) -> Result<()> {
+        ctx.accounts.mint_planet_nft_2022(args, &ctx.bumps)
+    }
+
+    /// Sets up a Bubblegum tree for compressed planets. The merkle tree
+    /// account must be allocated earlier in the same transaction.
+    pub fn create_planet_tree(
+        ctx: Context<CreatePlanetTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_planet_tree(max_depth, max_buffer_size, &ctx.bumps)
+    }
+
+    pub fn set_planet_tree_delegate(ctx: Context<SetPlanetTreeDelegate>) -> Result<()> {
+        ctx.accounts.set_planet_tree_delegate(&ctx.bumps)
+    }
+
+    /// Mints the planet as a compressed NFT in a program tree, which skips
+    /// the rent of a mint, token account and metadata account
+    pub fn mint_compressed_planet(
+        ctx: Context<MintCompressedPlanet>,
+        args: MintCompressedPlanetArgs,
+    ) -> Result<()> {
+        ctx.accounts.mint_compressed_planet(args, &ctx.bumps)
+    }
+
+    /// Escape hatch for soulbound planets, e.g. so a player who is moving
+    /// wallets can take their planet along
+    pub fn thaw_planet(ctx: Context<ThawPlanet>, planet_id: String) -> Result<()> {
+        ctx.accounts.thaw_planet(planet_id, &ctx.bumps)
+    }
+
+    /// `thaw_planet` for soulbound Token-2022 planets, which the mint
+    /// authority PDA froze directly
+    pub fn thaw_planet_2022(ctx: Context<ThawPlanet2022>, planet_id: String) -> Result<()> {
+        ctx.accounts.thaw_planet_2022(planet_id, &ctx.bumps)
+    }
+
+    /// Burns the holder's planet and refunds the rent of its accounts. The
+    /// planet can be minted again with a fresh attestation.
+    pub fn burn_planet(ctx: Context<BurnPlanet>) -> Result<()> {
+        ctx.accounts.burn_planet()
+    }
+
+    /// `burn_planet` for Token-2022 planets. Also closes the mint, which has
+    /// the mint authority PDA as its close authority.
+    pub fn burn_planet_2022(ctx: Context<BurnPlanet2022>) -> Result<()> {
+        ctx.accounts.burn_planet_2022(&ctx.bumps)
+    }
+
+    /// Moves a planet minted before NFTs went to the player's ATA out of the
+    /// token account owned by the `mint_authority` PDA. The program never
+    /// recorded who earned those planets, so the upgrade authority vouches for
+    /// `owner` by signing.
+    pub fn migrate_planet_to_owner(
+        ctx: Context<MigratePlanetToOwner>,
+        planet_id: String,
+    ) -> Result<()> {
+        ctx.accounts.migrate_planet_to_owner(planet_id, &ctx.bumps)
+    }
 }
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_BADGE_ID_LEN, MAX_BADGE_NAME_LEN, MAX_BADGE_URI_LEN};
+
+/// A badge players can claim once one of their `PlayerStats` counters reaches
+/// `threshold`, e.g. 10 correct guesses. Defined by the admin.
+/// Seeds: `["badge_kind", badge_id]`.
+#[account]
+#[derive(InitSpace)]
+pub struct BadgeKind {
+    #[max_len(MAX_BADGE_ID_LEN)]
+    pub badge_id: String,
+    #[max_len(MAX_BADGE_NAME_LEN)]
+    pub name: String,
+    #[max_len(MAX_BADGE_URI_LEN)]
+    pub uri: String,
+    pub stat: BadgeStat,
+    pub threshold: u64,
+    /// Badges of this kind claimed so far
+    pub claimed: u64,
+    pub bump: u8,
+}
+
+/// `PlayerStats` counter a badge is earned with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum BadgeStat {
+    CorrectGuesses,
+    CorrectEjections,
+    GamesPlayed,
+    Wins,
+    FlawlessWins,
+}
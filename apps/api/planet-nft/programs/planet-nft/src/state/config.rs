@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_PAYMENT_MINTS, MAX_PLANET_TREES};
+
+/// Program-wide settings, stored in the `["config"]` PDA
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    /// Key allowed to change the config and pause the program
+    pub admin: Pubkey,
+    /// Proposed new admin; becomes `admin` once it signs `accept_admin`
+    pub pending_admin: Option<Pubkey>,
+    /// Game server key that signs mint attestations for recorded wins
+    pub attestation_signer: Pubkey,
+    /// Emergency stop for minting
+    pub paused: bool,
+    /// Charged on every `mint_planet_nft` and paid into the treasury
+    pub mint_fee_lamports: u64,
+    /// SPL tokens accepted instead of the SOL fee
+    #[max_len(MAX_PAYMENT_MINTS)]
+    pub payment_mints: Vec<PaymentMint>,
+    /// Studio wallet listed as a creator on every planet
+    pub studio: Pubkey,
+    /// Royalty on secondary sales, in basis points
+    pub seller_fee_basis_points: u16,
+    /// Percentage of royalties paid to the player who discovered the planet;
+    /// the studio gets the rest. 0 leaves the player off the creators list.
+    pub discoverer_share: u8,
+    /// Most editions that can be printed from each new planet's master
+    /// edition. 0 disables printing.
+    pub max_prints: u64,
+    /// Paid to the planet's discoverer for every `print_planet_edition`
+    pub print_fee_lamports: u64,
+    /// Merkle trees created by `create_planet_tree`, the only ones
+    /// `mint_compressed_planet` mints into
+    #[max_len(MAX_PLANET_TREES)]
+    pub planet_trees: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// Fee for minting when paying with an SPL token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PaymentMint {
+    pub mint: Pubkey,
+    /// Amount in the token's base units
+    pub amount: u64,
+}
+
+impl Config {
+    pub fn payment_mint(&self, mint: &Pubkey) -> Option<&PaymentMint> {
+        self.payment_mints.iter().find(|p| &p.mint == mint)
+    }
+
+    /// Royalty split between the studio and (if the config gives discoverers
+    /// a share) the player who discovered the planet, as `(creator, share)`
+    pub fn royalty_shares(&self, discoverer: &Pubkey) -> Vec<(Pubkey, u8)> {
+        let discoverer_share = if discoverer == &self.studio {
+            // Metaplex rejects duplicate creators
+            0
+        } else {
+            self.discoverer_share
+        };
+
+        let mut shares = Vec::with_capacity(2);
+        if discoverer_share < 100 {
+            shares.push((self.studio, 100 - discoverer_share));
+        }
+        if discoverer_share > 0 {
+            shares.push((*discoverer, discoverer_share));
+        }
+        shares
+    }
+}
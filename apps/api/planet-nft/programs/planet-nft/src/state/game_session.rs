@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+/// On-chain copy of a row of the backend's `game_sessions` table, written by
+/// the game server. Seeds: `["game_session", player, game_id (u64 LE)]`.
+#[account]
+#[derive(InitSpace)]
+pub struct GameSession {
+    pub player: Pubkey,
+    pub game_id: u64,
+    pub start_slot: u64,
+    /// Slot `end_session` ran in, 0 while the game is in progress
+    pub end_slot: u64,
+    pub outcome: GameOutcome,
+    /// Index of the researcher the player picked as the real one
+    pub selected_researcher: u8,
+    /// `planet-generator` seed of the planet the player picked
+    pub selected_planet: u64,
+    /// `commit_game` hash of the game's secret, all zeros if the game wasn't
+    /// committed
+    pub commitment: [u8; 32],
+    /// Slot `commit_game` ran in, 0 if the game wasn't committed. Commits are
+    /// only accepted in `start_slot`.
+    pub commit_slot: u64,
+    /// Set once `reveal_game` has checked the secret against `commitment`
+    pub revealed: bool,
+    /// Set once the game's result has been added to the player's
+    /// `PlayerStats`
+    pub result_recorded: bool,
+    /// Set once a planet has been minted for this game, so each win pays
+    /// out only once
+    pub reward_claimed: bool,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum GameOutcome {
+    InProgress,
+    Won,
+    Lost,
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::LEADERBOARD_SIZE;
+
+/// Top players by one of the `PlayerStats` season counters, best first.
+/// Updated by `record_game_result` and cleared by the admin at the end of a
+/// season.
+/// Seeds: `["leaderboard_guesses"]` or `["leaderboard_ejections"]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub kind: LeaderboardKind,
+    /// Bumped by every reset
+    pub season: u32,
+    #[max_len(LEADERBOARD_SIZE)]
+    pub entries: Vec<LeaderboardEntry>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum LeaderboardKind {
+    CorrectGuesses,
+    CorrectEjections,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub score: u64,
+    /// Slot the player reached `score` in. Breaks ties, earliest first.
+    pub slot: u64,
+}
+
+impl Leaderboard {
+    /// Moves `player` to their place for `score`, or drops them if they
+    /// don't make the board
+    pub fn submit(&mut self, player: Pubkey, score: u64, slot: u64) {
+        if let Some(index) = self.entries.iter().position(|e| e.player == player) {
+            // Keep the earlier slot when the score didn't change
+            if self.entries[index].score == score {
+                return;
+            }
+            self.entries.remove(index);
+        }
+        if score == 0 {
+            return;
+        }
+
+        let index = self
+            .entries
+            .partition_point(|e| e.score > score || (e.score == score && e.slot <= slot));
+        if index < LEADERBOARD_SIZE {
+            self.entries.insert(
+                index,
+                LeaderboardEntry {
+                    player,
+                    score,
+                    slot,
+                },
+            );
+            self.entries.truncate(LEADERBOARD_SIZE);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+pub mod badge_kind;
+pub mod config;
+pub mod game_session;
+pub mod leaderboard;
+pub mod planet_state;
+pub mod player_planet;
+pub mod player_stats;
+pub mod treasury;
+
+pub use badge_kind::*;
+pub use config::*;
+pub use game_session::*;
+pub use leaderboard::*;
+pub use planet_state::*;
+pub use player_planet::*;
+pub use player_stats::*;
+pub use treasury::*;
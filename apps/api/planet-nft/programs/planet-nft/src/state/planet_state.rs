@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_COLOR_LEN, MAX_PLANET_ID_LEN};
+use crate::error::ErrorCode;
+
+/// Generated attributes of a minted planet, readable by other programs and
+/// indexers without going through the metadata URI.
+/// Seeds: `["planet_state", mint]`.
+#[account]
+#[derive(InitSpace)]
+pub struct PlanetState {
+    pub mint: Pubkey,
+    #[max_len(MAX_PLANET_ID_LEN)]
+    pub planet_id: String,
+    /// `planet-generator` seed the name and stats were derived from
+    pub seed: u64,
+    pub stats: PlanetStats,
+    pub bump: u8,
+}
+
+/// Planet attributes in the same ranges as the web generator
+/// (`planetGenerator.ts`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PlanetStats {
+    /// Average temperature in °F, 0-120
+    pub temperature_f: u8,
+    /// Ocean coverage in percent, 0-95
+    pub ocean_coverage: u8,
+    /// Surface gravity in hundredths of a g, 50-150 (0.5g-1.5g)
+    pub gravity_centi_g: u16,
+    /// Color descriptor, e.g. "Bluish green"
+    #[max_len(MAX_COLOR_LEN)]
+    pub color: String,
+}
+
+impl PlanetStats {
+    pub const MAX_TEMPERATURE_F: u8 = planet_generator::MAX_TEMPERATURE_F;
+    pub const MAX_OCEAN_COVERAGE: u8 = planet_generator::MAX_OCEAN_COVERAGE;
+    pub const MIN_GRAVITY_CENTI_G: u16 = planet_generator::MIN_GRAVITY_CENTI_G;
+    pub const MAX_GRAVITY_CENTI_G: u16 = planet_generator::MAX_GRAVITY_CENTI_G;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.temperature_f <= Self::MAX_TEMPERATURE_F,
+            ErrorCode::TemperatureOutOfRange
+        );
+        require!(
+            self.ocean_coverage <= Self::MAX_OCEAN_COVERAGE,
+            ErrorCode::OceanCoverageOutOfRange
+        );
+        require!(
+            (Self::MIN_GRAVITY_CENTI_G..=Self::MAX_GRAVITY_CENTI_G).contains(&self.gravity_centi_g),
+            ErrorCode::GravityOutOfRange
+        );
+        require!(
+            !self.color.is_empty() && self.color.len() <= MAX_COLOR_LEN,
+            ErrorCode::InvalidColor
+        );
+        Ok(())
+    }
+
+    /// Re-derives the planet from `seed` and rejects a name or stat that
+    /// doesn't match, so only planets the generator can produce are minted
+    pub fn verify_seed(&self, seed: u64, planet_name: &str) -> Result<()> {
+        let planet = planet_generator::generate_planet(seed);
+        require!(
+            planet.name.to_string() == planet_name,
+            ErrorCode::PlanetNameMismatch
+        );
+        require!(
+            self.temperature_f == planet.temperature_f,
+            ErrorCode::TemperatureMismatch
+        );
+        require!(
+            self.ocean_coverage == planet.ocean_coverage,
+            ErrorCode::OceanCoverageMismatch
+        );
+        require!(
+            self.gravity_centi_g == planet.gravity_centi_g,
+            ErrorCode::GravityMismatch
+        );
+        require!(self.color == planet.color, ErrorCode::ColorMismatch);
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_PLANET_ID_LEN;
+
+/// A player's claim on a planet, mirroring a row of the backend's
+/// `planet_nfts` table. Seeds: `["player_planet", player, planet_id]`.
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerPlanet {
+    pub player: Pubkey,
+    #[max_len(MAX_PLANET_ID_LEN)]
+    pub planet_id: String,
+    /// Mint of the player's copy of the planet NFT
+    pub mint: Pubkey,
+    /// Slot the planet was minted in
+    pub earned_slot: u64,
+    /// Game session the planet was won in
+    pub game_id: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use crate::attestation::GameResultAttestation;
+use crate::error::ErrorCode;
+use crate::state::BadgeStat;
+
+/// A player's lifetime record, the on-chain counterpart of the backend's
+/// `player_stats` table, plus the counters of the current leaderboard season.
+/// Only changed by `record_game_result`.
+/// Seeds: `["player_stats", player]`.
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerStats {
+    pub player: Pubkey,
+    pub correct_guesses: u64,
+    pub correct_ejections: u64,
+    pub incorrect_guesses: u64,
+    /// Ejections of the researcher's planet
+    pub incorrect_ejections: u64,
+    pub games_played: u64,
+    pub wins: u64,
+    /// Wins without an incorrect ejection
+    pub flawless_wins: u64,
+    /// Leaderboard season the `season_*` counters belong to
+    pub season: u32,
+    /// Correct guesses this season, ranked on the guesses leaderboard
+    pub season_correct_guesses: u64,
+    /// Correct ejections this season, ranked on the ejections leaderboard
+    pub season_correct_ejections: u64,
+    pub last_updated_slot: u64,
+    pub bump: u8,
+}
+
+impl PlayerStats {
+    /// Adds one game to the lifetime and season counters. `season` is the
+    /// leaderboards' current season; the season counters start over when
+    /// the player's lag behind it.
+    pub fn record(
+        &mut self,
+        result: &GameResultAttestation,
+        won: bool,
+        season: u32,
+        slot: u64,
+    ) -> Result<()> {
+        if self.season < season {
+            self.season = season;
+            self.season_correct_guesses = 0;
+            self.season_correct_ejections = 0;
+        }
+        self.season_correct_guesses = self
+            .season_correct_guesses
+            .checked_add(result.correct_guesses.into())
+            .ok_or(ErrorCode::StatsOverflow)?;
+        self.season_correct_ejections = self
+            .season_correct_ejections
+            .checked_add(result.correct_ejections.into())
+            .ok_or(ErrorCode::StatsOverflow)?;
+
+        self.correct_guesses = self
+            .correct_guesses
+            .checked_add(result.correct_guesses.into())
+            .ok_or(ErrorCode::StatsOverflow)?;
+        self.correct_ejections = self
+            .correct_ejections
+            .checked_add(result.correct_ejections.into())
+            .ok_or(ErrorCode::StatsOverflow)?;
+        self.incorrect_guesses = self
+            .incorrect_guesses
+            .checked_add(result.incorrect_guesses.into())
+            .ok_or(ErrorCode::StatsOverflow)?;
+        self.incorrect_ejections = self
+            .incorrect_ejections
+            .checked_add(result.incorrect_ejections.into())
+            .ok_or(ErrorCode::StatsOverflow)?;
+        self.games_played = self
+            .games_played
+            .checked_add(1)
+            .ok_or(ErrorCode::StatsOverflow)?;
+        if won {
+            self.wins = self.wins.checked_add(1).ok_or(ErrorCode::StatsOverflow)?;
+            if result.incorrect_ejections == 0 {
+                self.flawless_wins = self
+                    .flawless_wins
+                    .checked_add(1)
+                    .ok_or(ErrorCode::StatsOverflow)?;
+            }
+        }
+        self.last_updated_slot = slot;
+        Ok(())
+    }
+
+    pub fn get(&self, stat: BadgeStat) -> u64 {
+        match stat {
+            BadgeStat::CorrectGuesses => self.correct_guesses,
+            BadgeStat::CorrectEjections => self.correct_ejections,
+            BadgeStat::GamesPlayed => self.games_played,
+            BadgeStat::Wins => self.wins,
+            BadgeStat::FlawlessWins => self.flawless_wins,
+        }
+    }
+}
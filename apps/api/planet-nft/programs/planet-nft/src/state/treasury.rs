@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+/// Program-owned vault for mint fees. Seeds: `["treasury"]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub bump: u8,
+}
@@ -1,11 +1,179 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
 import { PlanetNft } from "../target/types/planet_nft";
-import { PublicKey, Keypair } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
+import {
+  PublicKey,
+  Keypair,
+  Ed25519Program,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddress,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  getTokenMetadata,
+  mintTo,
+} from "@solana/spl-token";
+import { createAllocTreeIx } from "@solana/spl-account-compression";
 import { expect } from "chai";
+import { createHash, randomBytes } from "crypto";
+import gameServerSecret from "./fixtures/game-server.json";
+import legacyTokenAccountFixture from "./fixtures/legacy-token-account.json";
 
 const METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+const BUBBLEGUM_PROGRAM_ID = new PublicKey("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+const COMPRESSION_PROGRAM_ID = new PublicKey("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+const NOOP_PROGRAM_ID = new PublicKey("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+// Game server key the tests register as the config's attestation signer
+const gameServer = Keypair.fromSecretKey(Uint8Array.from(gameServerSecret));
+
+// Borsh encoding of the program's MintAttestation struct
+function encodeAttestation(
+  planetId: string,
+  recipient: PublicKey,
+  gameId: BN,
+  seed: BN,
+  metadataUri: string,
+  expiry: BN,
+  nonce: BN,
+  soulbound: boolean
+): Buffer {
+  const str = (value: string) => {
+    const bytes = Buffer.from(value);
+    const len = Buffer.alloc(4);
+    len.writeUInt32LE(bytes.length);
+    return Buffer.concat([len, bytes]);
+  };
+  return Buffer.concat([
+    str(planetId),
+    recipient.toBuffer(),
+    gameId.toArrayLike(Buffer, "le", 8),
+    seed.toArrayLike(Buffer, "le", 8),
+    str(metadataUri),
+    expiry.toArrayLike(Buffer, "le", 8),
+    nonce.toArrayLike(Buffer, "le", 8),
+    Buffer.from([soulbound ? 1 : 0]),
+  ]);
+}
+
+// Golden vectors from crates/planet-generator's tests, so the tests can pass
+// the name and stats the program re-derives from a seed
+const GOLDEN_PLANETS: Record<string, { planetName: string; stats: { temperatureF: number; oceanCoverage: number; gravityCentiG: number; color: string } }> = {
+  "7": { planetName: "Nexthos Nexus", stats: { temperatureF: 54, oceanCoverage: 23, gravityCentiG: 97, color: "Crimson with dark patches" } },
+  "42": { planetName: "Xenmora", stats: { temperatureF: 4, oceanCoverage: 83, gravityCentiG: 72, color: "Maroon red with black craters" } },
+  "1337": { planetName: "Qualius Ridge", stats: { temperatureF: 27, oceanCoverage: 63, gravityCentiG: 87, color: "Pale icy teal" } },
+};
+
+// commit_game's sha256(researcher_index || planet_seeds as u64 LE || salt)
+function gameCommitment(researcherIndex: number, planetSeeds: BN[], salt: Buffer): Buffer {
+  return createHash("sha256")
+    .update(
+      Buffer.concat([
+        Buffer.from([researcherIndex]),
+        ...planetSeeds.map((planetSeed) => planetSeed.toArrayLike(Buffer, "le", 8)),
+        salt,
+      ])
+    )
+    .digest();
+}
+
+function generatePlanet(seed: BN) {
+  const planet = GOLDEN_PLANETS[seed.toString()];
+  if (!planet) {
+    throw new Error(`No golden planet for seed ${seed}`);
+  }
+  return { planetName: planet.planetName, stats: { ...planet.stats } };
+}
+
+// Borsh encoding of the program's GameResultAttestation struct
+function encodeGameResult(
+  player: PublicKey,
+  gameId: BN,
+  counts: {
+    correctGuesses: number;
+    correctEjections: number;
+    incorrectGuesses: number;
+    incorrectEjections: number;
+  }
+): Buffer {
+  const u32 = (value: number) => {
+    const buf = Buffer.alloc(4);
+    buf.writeUInt32LE(value);
+    return buf;
+  };
+  return Buffer.concat([
+    player.toBuffer(),
+    gameId.toArrayLike(Buffer, "le", 8),
+    u32(counts.correctGuesses),
+    u32(counts.correctEjections),
+    u32(counts.incorrectGuesses),
+    u32(counts.incorrectEjections),
+  ]);
+}
+
+const metadataPda = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    METADATA_PROGRAM_ID
+  )[0];
+
+const masterEditionPda = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      Buffer.from("edition"),
+    ],
+    METADATA_PROGRAM_ID
+  )[0];
+
+// Reads the fields the tests care about out of a Metaplex metadata account
+function decodeMetadata(data: Buffer) {
+  let offset = 1 + 32 + 32; // key, update authority, mint
+  const str = () => {
+    const len = data.readUInt32LE(offset);
+    const value = data.subarray(offset + 4, offset + 4 + len).toString().replace(/\0+$/, "");
+    offset += 4 + len;
+    return value;
+  };
+  const name = str();
+  const symbol = str();
+  const uri = str();
+  const sellerFeeBasisPoints = data.readUInt16LE(offset);
+  offset += 2;
+  const creators: { address: PublicKey; verified: boolean; share: number }[] = [];
+  if (data[offset++] === 1) {
+    const count = data.readUInt32LE(offset);
+    offset += 4;
+    for (let i = 0; i < count; i++) {
+      creators.push({
+        address: new PublicKey(data.subarray(offset, offset + 32)),
+        verified: data[offset + 32] === 1,
+        share: data[offset + 33],
+      });
+      offset += 34;
+    }
+  }
+  offset += 1; // primary sale happened
+  const isMutable = data[offset++] === 1;
+  if (data[offset++] === 1) offset += 1; // edition nonce
+  if (data[offset++] === 1) offset += 1; // token standard
+  let collection: { verified: boolean; key: PublicKey } | null = null;
+  if (data[offset++] === 1) {
+    collection = {
+      verified: data[offset] === 1,
+      key: new PublicKey(data.subarray(offset + 1, offset + 33)),
+    };
+  }
+  return { name, symbol, uri, sellerFeeBasisPoints, creators, isMutable, collection };
+}
 
 describe("planet-nft", () => {
   // Configure the client to use the local cluster.
@@ -15,14 +183,175 @@ describe("planet-nft", () => {
   const program = anchor.workspace.PlanetNft as Program<PlanetNft>;
   const payer = provider.wallet;
 
-  it("Mints a planet NFT", async () => {
-    const planetId = "test_planet_123";
-    const planetName = "Test Planet";
-    const metadataUri = "https://placeholder.metadata/test_planet_123";
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+
+  const [treasuryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    program.programId
+  );
+
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    anchor.web3.BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+
+  const [programAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("authority")],
+    program.programId
+  );
+
+  const [collectionMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("collection")],
+    program.programId
+  );
+
+  // Seed of the test planets, and the name and attributes generated from it
+  const seed = new BN(42);
+  const { planetName, stats } = generatePlanet(seed);
+
+  // Every mint redeems a game session of its own
+  let lastGameId = 0;
+
+  // Mint of the first test planet, reused by later tests
+  let firstPlanetMint: PublicKey;
+  let firstPlanetGameId: BN;
+
+  // Receives the legacy planet from the fixtures, which has no PlayerPlanet
+  // record
+  const legacyOwner = Keypair.generate();
+
+  const inOneHour = () => new BN(Math.floor(Date.now() / 1000) + 3600);
+
+  function attest(
+    signer: Keypair,
+    planetId: string,
+    recipient: PublicKey,
+    gameId: BN,
+    seed: BN,
+    metadataUri: string,
+    expiry: BN,
+    nonce: BN,
+    soulbound = false
+  ): TransactionInstruction {
+    return Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: signer.secretKey,
+      message: encodeAttestation(planetId, recipient, gameId, seed, metadataUri, expiry, nonce, soulbound),
+    });
+  }
+
+  const gameSessionPda = (player: PublicKey, gameId: BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("game_session"), player.toBuffer(), gameId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  // Records a finished game for `player` the way the game server does. The
+  // player picks researcher 3, whose planet is `seed`; a win is committed as
+  // the session starts and then revealed, a loss is just ended.
+  async function playGame(player: PublicKey, outcome: object = { won: {} }) {
+    const gameId = new BN(++lastGameId);
+    const gameSession = gameSessionPda(player, gameId);
+    const sessionAccounts = { config: configPda, gameServer: gameServer.publicKey, player, gameSession };
+    const startSession = await program.methods
+      .startSession(gameId)
+      .accounts({ ...sessionAccounts, payer: payer.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+      .instruction();
+
+    if ("lost" in outcome) {
+      await program.methods
+        .endSession({ gameId, outcome, selectedResearcher: 3, selectedPlanet: seed })
+        .accounts(sessionAccounts)
+        .preInstructions([startSession])
+        .signers([gameServer])
+        .rpc();
+      return { gameId, gameSession };
+    }
+
+    const researcherIndex = 3;
+    const planetSeeds = [new BN(1000), new BN(1001), new BN(1002), seed];
+    const salt = randomBytes(32);
+    await program.methods
+      .commitGame(gameId, [...gameCommitment(researcherIndex, planetSeeds, salt)])
+      .accounts(sessionAccounts)
+      .preInstructions([startSession])
+      .signers([gameServer])
+      .rpc();
+    await program.methods
+      .revealGame({ gameId, researcherIndex, planetSeeds, salt: [...salt], selectedResearcher: researcherIndex })
+      .accounts(sessionAccounts)
+      .signers([gameServer])
+      .rpc();
+
+    return { gameId, gameSession };
+  }
+
+  const [guessesLeaderboard] = PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard_guesses")],
+    program.programId
+  );
+
+  const [ejectionsLeaderboard] = PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard_ejections")],
+    program.programId
+  );
+
+  const playerStatsPda = (player: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("player_stats"), player.toBuffer()],
+      program.programId
+    )[0];
+
+  // Submits a game result attested by `signer`
+  function recordGameResult(
+    player: PublicKey,
+    gameId: BN,
+    counts: {
+    correctGuesses: number;
+    correctEjections: number;
+    incorrectGuesses: number;
+    incorrectEjections: number;
+  },
+    signer: Keypair = gameServer
+  ) {
+    return program.methods
+      .recordGameResult({ gameId, ...counts })
+      .accounts({
+        config: configPda,
+        player,
+        gameSession: gameSessionPda(player, gameId),
+        playerStats: playerStatsPda(player),
+        guessesLeaderboard,
+        ejectionsLeaderboard,
+        payer: payer.publicKey,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: signer.secretKey,
+          message: encodeGameResult(player, gameId, counts),
+        }),
+      ])
+      .rpc();
+  }
+
+  async function mintAccounts(
+    planetId: string,
+    recipient: PublicKey,
+    feePayer: PublicKey = payer.publicKey
+  ) {
+    const { gameId, gameSession } = await playGame(recipient);
+
+    // Every copy of a planet gets a fresh mint keypair
+    const mint = Keypair.generate();
+    const mintPda = mint.publicKey;
 
     // Derive PDAs
-    const [mintPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("planet_nft"), Buffer.from(planetId)],
+    const [playerPlanetPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("player_planet"), recipient.toBuffer(), Buffer.from(planetId)],
       program.programId
     );
 
@@ -31,42 +360,2180 @@ describe("planet-nft", () => {
       program.programId
     );
 
-    const tokenAccount = await getAssociatedTokenAddress(
-      mintPda,
-      payer.publicKey
+    const [planetStatePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("planet_state"), mintPda.toBuffer()],
+      program.programId
     );
 
-    // Derive metadata PDA
-    const [metadataPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        METADATA_PROGRAM_ID.toBuffer(),
-        mintPda.toBuffer(),
-      ],
-      METADATA_PROGRAM_ID
-    );
+    const tokenAccount = await getAssociatedTokenAddress(mintPda, recipient);
+
+    const accounts = {
+      config: configPda,
+      treasury: treasuryPda,
+      playerPlanet: playerPlanetPda,
+      gameSession,
+      mint: mintPda,
+      planetState: planetStatePda,
+      mintAuthority: mintAuthorityPda,
+      recipient,
+      tokenAccount,
+      metadata: metadataPda(mintPda),
+      masterEdition: masterEditionPda(mintPda),
+      programAuthority,
+      collectionMint,
+      collectionMetadata: metadataPda(collectionMint),
+      collectionMasterEdition: masterEditionPda(collectionMint),
+      tokenMetadataProgram: METADATA_PROGRAM_ID,
+      payer: feePayer,
+      // Optional token payment accounts; the SOL fee applies when left out
+      paymentMint: null,
+      payerPaymentAccount: null,
+      treasuryPaymentAccount: null,
+      instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    };
+
+    return { mint, accounts, gameId };
+  }
+
+  async function expectError(promise: Promise<unknown>, code: string) {
+    try {
+      await promise;
+      expect.fail(`expected ${code}`);
+    } catch (err) {
+      expect(err.error?.errorCode?.code).to.equal(code);
+    }
+  }
+
+  before(async () => {
+    await program.methods
+      .initializeConfig(payer.publicKey, gameServer.publicKey)
+      .accounts({
+        config: configPda,
+        treasury: treasuryPda,
+        program: program.programId,
+        programData,
+        authority: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .createCollection("Planets", "https://placeholder.metadata/collection")
+      .accounts({
+        config: configPda,
+        programAuthority,
+        collectionMint,
+        collectionTokenAccount: await getAssociatedTokenAddress(
+          collectionMint,
+          programAuthority,
+          true
+        ),
+        collectionMetadata: metadataPda(collectionMint),
+        collectionMasterEdition: masterEditionPda(collectionMint),
+        tokenMetadataProgram: METADATA_PROGRAM_ID,
+        admin: payer.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    await program.methods
+      .createLeaderboards()
+      .accounts({
+        config: configPda,
+        guessesLeaderboard,
+        ejectionsLeaderboard,
+        admin: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Mints a planet NFT", async () => {
+    const planetId = "test_planet_123";
+    const metadataUri = "https://placeholder.metadata/test_planet_123";
+    const expiry = inOneHour();
+    const nonce = new BN(1);
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
 
     try {
       const tx = await program.methods
-        .mintPlanetNft(planetId, planetName, metadataUri)
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+
+      console.log("Transaction signature:", tx);
+      console.log("Mint:", accounts.mint.toString());
+      firstPlanetMint = accounts.mint;
+      firstPlanetGameId = gameId;
+
+      const account = await getAccount(provider.connection, accounts.tokenAccount);
+      expect(account.owner.toBase58()).to.equal(payer.publicKey.toBase58());
+      expect(Number(account.amount)).to.equal(1);
+    } catch (err) {
+      console.error("Error:", err);
+      throw err;
+    }
+  });
+
+  it("Mints a planet NFT to a named recipient", async () => {
+    const planetId = "test_planet_456";
+    const metadataUri = "https://placeholder.metadata/test_planet_456";
+    const expiry = inOneHour();
+    const nonce = new BN(2);
+    const recipient = Keypair.generate();
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, recipient.publicKey);
+
+    await program.methods
+      .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+      .accounts(accounts)
+      .signers([mint])
+      .preInstructions([
+        attest(gameServer, planetId, recipient.publicKey, gameId, seed, metadataUri, expiry, nonce),
+      ])
+      .rpc();
+
+    const account = await getAccount(provider.connection, accounts.tokenAccount);
+    expect(account.owner.toBase58()).to.equal(recipient.publicKey.toBase58());
+    expect(Number(account.amount)).to.equal(1);
+  });
+
+  it("Verifies every planet into the collection", async () => {
+    const info = await provider.connection.getAccountInfo(metadataPda(firstPlanetMint));
+    const { collection } = decodeMetadata(info.data);
+    expect(collection.key.toBase58()).to.equal(collectionMint.toBase58());
+    expect(collection.verified).to.be.true;
+  });
+
+  it("Stores the planet's attributes in its PlanetState", async () => {
+    const planetState = await program.account.planetState.fetch(
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("planet_state"), firstPlanetMint.toBuffer()],
+        program.programId
+      )[0]
+    );
+    expect(planetState.mint.toBase58()).to.equal(firstPlanetMint.toBase58());
+    expect(planetState.planetId).to.equal("test_planet_123");
+    expect(planetState.seed.eq(seed)).to.be.true;
+    expect(planetState.stats).to.deep.equal(stats);
+  });
+
+  it("Rejects a name or attributes that don't match the seed", async () => {
+    const cases = [
+      { planetName: "Earth Prime", stats, error: "PlanetNameMismatch" },
+      {
+        planetName,
+        stats: { ...stats, temperatureF: (stats.temperatureF + 1) % 121 },
+        error: "TemperatureMismatch",
+      },
+      {
+        planetName,
+        stats: { ...stats, oceanCoverage: (stats.oceanCoverage + 1) % 96 },
+        error: "OceanCoverageMismatch",
+      },
+      {
+        planetName,
+        stats: { ...stats, gravityCentiG: stats.gravityCentiG === 150 ? 149 : stats.gravityCentiG + 1 },
+        error: "GravityMismatch",
+      },
+      {
+        planetName,
+        stats: { ...stats, color: stats.color === "Bluish green" ? "Rust red" : "Bluish green" },
+        error: "ColorMismatch",
+      },
+    ];
+
+    for (const [i, { planetName, stats, error }] of cases.entries()) {
+      const planetId = `forged_planet_${i}`;
+      const metadataUri = `https://placeholder.metadata/${planetId}`;
+      const expiry = inOneHour();
+      const nonce = new BN(23 + i);
+
+      const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+      await expectError(
+        program.methods
+          .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+          .accounts(accounts)
+          .signers([mint])
+          .preInstructions([
+            attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+          ])
+          .rpc(),
+        error
+      );
+    }
+  });
+
+  it("Rejects attributes outside the generator's ranges", async () => {
+    const planetId = "heavy_planet";
+    const metadataUri = "https://placeholder.metadata/heavy_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(22);
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+    await expectError(
+      program.methods
+        .mintPlanetNft({
+          planetId,
+          seed,
+          planetName,
+          metadataUri,
+          gameId,
+          expiry,
+          nonce,
+          soulbound: false,
+          stats: { ...stats, gravityCentiG: 151 },
+        })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc(),
+      "GravityOutOfRange"
+    );
+  });
+
+  it("Lets the admin or game server update planet metadata", async () => {
+    const planetId = "test_planet_123";
+    const metadataUri = "https://arweave.net/test_planet_123";
+    const [mintAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), Buffer.from(planetId)],
+      program.programId
+    );
+    const accounts = {
+      config: configPda,
+      mintAuthority,
+      metadata: metadataPda(firstPlanetMint),
+      tokenMetadataProgram: METADATA_PROGRAM_ID,
+    };
+
+    const intruder = Keypair.generate();
+    await expectError(
+      program.methods
+        .updatePlanetMetadata({ planetId, metadataUri })
+        .accounts({ ...accounts, authority: intruder.publicKey })
+        .signers([intruder])
+        .rpc(),
+      "Unauthorized"
+    );
+
+    await program.methods
+      .updatePlanetMetadata({ planetId, metadataUri })
+      .accounts({ ...accounts, authority: gameServer.publicKey })
+      .signers([gameServer])
+      .rpc();
+
+    const info = await provider.connection.getAccountInfo(accounts.metadata);
+    const metadata = decodeMetadata(info.data);
+    expect(metadata.uri).to.equal(metadataUri);
+    expect(metadata.name).to.equal(planetName);
+    expect(metadata.isMutable).to.be.true;
+    expect(metadata.collection.verified).to.be.true;
+  });
+
+  it("Hands mint authority to the master edition", async () => {
+    const mint = await getMint(provider.connection, firstPlanetMint);
+    expect(Number(mint.supply)).to.equal(1);
+    expect(mint.mintAuthority.toBase58()).to.equal(
+      masterEditionPda(firstPlanetMint).toBase58()
+    );
+  });
+
+  it("Rejects a mint without a game server attestation", async () => {
+    const planetId = "unattested_planet";
+    const metadataUri = "https://placeholder.metadata/unattested_planet";
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+    await expectError(
+      program.methods
+        .mintPlanetNft({
+          planetId,
+          seed,
+          planetName,
+          metadataUri,
+          gameId,
+          expiry: inOneHour(),
+          nonce: new BN(3),
+          soulbound: false,
+          stats,
+        })
+        .accounts(accounts)
+        .signers([mint])
+        .rpc(),
+      "MissingAttestation"
+    );
+  });
+
+  it("Rejects an attestation signed by the wrong key", async () => {
+    const planetId = "forged_planet";
+    const metadataUri = "https://placeholder.metadata/forged_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(4);
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+    await expectError(
+      program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(Keypair.generate(), planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc(),
+      "InvalidAttestationSigner"
+    );
+  });
+
+  it("Rejects an expired attestation", async () => {
+    const planetId = "expired_planet";
+    const metadataUri = "https://placeholder.metadata/expired_planet";
+    const expiry = new BN(Math.floor(Date.now() / 1000) - 60);
+    const nonce = new BN(5);
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+    await expectError(
+      program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc(),
+      "AttestationExpired"
+    );
+  });
+
+  it("Rejects an attestation for a different recipient", async () => {
+    const planetId = "stolen_planet";
+    const metadataUri = "https://placeholder.metadata/stolen_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(6);
+    const winner = Keypair.generate();
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+    await expectError(
+      program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, winner.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc(),
+      "AttestationMismatch"
+    );
+  });
+
+  it("Rejects an attestation signed for a different game", async () => {
+    const planetId = "other_game_planet";
+    const metadataUri = "https://placeholder.metadata/other_game_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(38);
+
+    // Two won games; the attestation only covers the first one
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+    const other = await playGame(payer.publicKey);
+
+    await expectError(
+      program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId: other.gameId, expiry, nonce, soulbound: false, stats })
+        .accounts({ ...accounts, gameSession: other.gameSession })
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc(),
+      "AttestationMismatch"
+    );
+  });
+
+  it("Only migrates planets minted by the legacy program", async () => {
+    const planetId = "test_planet_123";
+
+    const [mintAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), Buffer.from(planetId)],
+      program.programId
+    );
+
+    // Minted by the current program, so it already lives in the payer's ATA
+    const ownerTokenAccount = await getAssociatedTokenAddress(
+      firstPlanetMint,
+      payer.publicKey
+    );
+
+    await expectError(
+      program.methods
+        .migratePlanetToOwner(planetId)
         .accounts({
-          mint: mintPda,
+          mint: firstPlanetMint,
           mintAuthority: mintAuthorityPda,
-          tokenAccount: tokenAccount,
-          metadata: metadataPda,
-          tokenMetadataProgram: METADATA_PROGRAM_ID,
+          legacyTokenAccount: ownerTokenAccount,
+          owner: payer.publicKey,
+          ownerTokenAccount: ownerTokenAccount,
+          program: program.programId,
+          programData,
+          authority: payer.publicKey,
           payer: payer.publicKey,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
           systemProgram: anchor.web3.SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .rpc(),
+      "ConstraintSeeds"
+    );
+  });
+
+  it("Moves a legacy planet to its owner and refunds the old account", async () => {
+    // Loaded into the validator from tests/fixtures (see Anchor.toml)
+    const planetId = "legacy_planet";
+    const legacyTokenAccount = new PublicKey(legacyTokenAccountFixture.pubkey);
+
+    const [legacyMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("planet_nft"), Buffer.from(planetId)],
+      program.programId
+    );
+    const [mintAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), Buffer.from(planetId)],
+      program.programId
+    );
+
+    const owner = legacyOwner.publicKey;
+    const ownerTokenAccount = await getAssociatedTokenAddress(legacyMint, owner);
+    const rent = (await provider.connection.getAccountInfo(legacyTokenAccount)).lamports;
+
+    await program.methods
+      .migratePlanetToOwner(planetId)
+      .accounts({
+        mint: legacyMint,
+        mintAuthority: mintAuthorityPda,
+        legacyTokenAccount,
+        owner,
+        ownerTokenAccount,
+        program: program.programId,
+        programData,
+        authority: payer.publicKey,
+        payer: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const account = await getAccount(provider.connection, ownerTokenAccount);
+    expect(account.owner.toBase58()).to.equal(owner.toBase58());
+    expect(Number(account.amount)).to.equal(1);
+
+    expect(await provider.connection.getAccountInfo(legacyTokenAccount)).to.be.null;
+    expect(await provider.connection.getBalance(owner)).to.equal(rent);
+  });
+
+  it("Records the player's claim and rejects a second one", async () => {
+    const planetId = "test_planet_123";
+    const metadataUri = "https://placeholder.metadata/test_planet_123";
+    const expiry = inOneHour();
+    const nonce = new BN(8);
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+    const record = await program.account.playerPlanet.fetch(accounts.playerPlanet);
+    expect(record.player.toBase58()).to.equal(payer.publicKey.toBase58());
+    expect(record.planetId).to.equal(planetId);
+    expect(record.mint.toBase58()).to.equal(firstPlanetMint.toBase58());
+    expect(record.gameId.toNumber()).to.equal(firstPlanetGameId.toNumber());
+
+    await expectError(
+      program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc(),
+      "PlanetAlreadyClaimed"
+    );
+  });
+
+  it("Lets another player claim their own copy of a planet", async () => {
+    const planetId = "test_planet_123";
+    const metadataUri = "https://placeholder.metadata/test_planet_123";
+    const expiry = inOneHour();
+    const nonce = new BN(9);
+    const otherPlayer = Keypair.generate();
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, otherPlayer.publicKey);
+
+    await program.methods
+      .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+      .accounts(accounts)
+      .signers([mint])
+      .preInstructions([
+        attest(gameServer, planetId, otherPlayer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+      ])
+      .rpc();
+
+    const record = await program.account.playerPlanet.fetch(accounts.playerPlanet);
+    expect(record.player.toBase58()).to.equal(otherPlayer.publicKey.toBase58());
+    expect(record.mint.toBase58()).to.not.equal(firstPlanetMint.toBase58());
+  });
+
+  it("Records game sessions from the game server only", async () => {
+    const { gameId, gameSession } = await playGame(payer.publicKey, { lost: {} });
+
+    const session = await program.account.gameSession.fetch(gameSession);
+    expect(session.player.toBase58()).to.equal(payer.publicKey.toBase58());
+    expect(session.gameId.eq(gameId)).to.be.true;
+    expect(session.startSlot.toNumber()).to.be.greaterThan(0);
+    expect(session.endSlot.gte(session.startSlot)).to.be.true;
+    expect(session.outcome).to.deep.equal({ lost: {} });
+    expect(session.selectedResearcher).to.equal(3);
+    expect(session.selectedPlanet.eq(seed)).to.be.true;
+    expect(session.rewardClaimed).to.be.false;
+
+    await expectError(
+      program.methods
+        .endSession({ gameId, outcome: { won: {} }, selectedResearcher: 3, selectedPlanet: seed })
+        .accounts({
+          config: configPda,
+          gameServer: gameServer.publicKey,
+          player: payer.publicKey,
+          gameSession,
+        })
+        .signers([gameServer])
+        .rpc(),
+      "SessionAlreadyEnded"
+    );
+
+    const player = Keypair.generate();
+    await expectError(
+      program.methods
+        .startSession(new BN(1))
+        .accounts({
+          config: configPda,
+          gameServer: player.publicKey,
+          player: player.publicKey,
+          gameSession: gameSessionPda(player.publicKey, new BN(1)),
+          payer: payer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc(),
+      "Unauthorized"
+    );
+  });
+
+  it("Commits a game before play and reveals the outcome", async () => {
+    const player = Keypair.generate().publicKey;
+    const gameId = new BN(++lastGameId);
+    const gameSession = gameSessionPda(player, gameId);
+    const sessionAccounts = { config: configPda, gameServer: gameServer.publicKey, player, gameSession };
+
+    const researcherIndex = 6;
+    const planetSeeds = Array.from({ length: 10 }, (_, i) => new BN(1000 + i));
+    const salt = randomBytes(32);
+    const commitment = gameCommitment(researcherIndex, planetSeeds, salt);
+
+    // The commit has to land in the slot the session starts in
+    await program.methods
+      .commitGame(gameId, [...commitment])
+      .accounts(sessionAccounts)
+      .preInstructions([
+        await program.methods
+          .startSession(gameId)
+          .accounts({ ...sessionAccounts, payer: payer.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+          .instruction(),
+      ])
+      .signers([gameServer])
+      .rpc();
+    const committed = await program.account.gameSession.fetch(gameSession);
+    expect(committed.commitSlot.eq(committed.startSlot)).to.be.true;
+
+    // The server can no longer just declare an outcome, or change the secret
+    await expectError(
+      program.methods
+        .endSession({ gameId, outcome: { won: {} }, selectedResearcher: 2, selectedPlanet: planetSeeds[2] })
+        .accounts(sessionAccounts)
+        .signers([gameServer])
+        .rpc(),
+      "SessionCommitted"
+    );
+    await expectError(
+      program.methods
+        .revealGame({ gameId, researcherIndex: 2, planetSeeds, salt: [...salt], selectedResearcher: 2 })
+        .accounts(sessionAccounts)
+        .signers([gameServer])
+        .rpc(),
+      "CommitmentMismatch"
+    );
+
+    await program.methods
+      .revealGame({ gameId, researcherIndex, planetSeeds, salt: [...salt], selectedResearcher: researcherIndex })
+      .accounts(sessionAccounts)
+      .signers([gameServer])
+      .rpc();
+
+    const session = await program.account.gameSession.fetch(gameSession);
+    expect(session.outcome).to.deep.equal({ won: {} });
+    expect(session.revealed).to.be.true;
+    expect(session.commitment).to.deep.equal([...commitment]);
+    expect(session.selectedPlanet.eq(planetSeeds[researcherIndex])).to.be.true;
+  });
+
+  it("Only pays out wins revealed against a commitment", async () => {
+    const planetId = "unrevealed_planet";
+    const metadataUri = "https://placeholder.metadata/unrevealed_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(39);
+    const { accounts, mint } = await mintAccounts(planetId, payer.publicKey);
+
+    // Without a commitment the server can't declare a win
+    const gameId = new BN(++lastGameId);
+    const gameSession = gameSessionPda(payer.publicKey, gameId);
+    const sessionAccounts = { config: configPda, gameServer: gameServer.publicKey, player: payer.publicKey, gameSession };
+    const startSession = () =>
+      program.methods
+        .startSession(gameId)
+        .accounts({ ...sessionAccounts, payer: payer.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+        .instruction();
+    await expectError(
+      program.methods
+        .endSession({ gameId, outcome: { won: {} }, selectedResearcher: 3, selectedPlanet: seed })
+        .accounts(sessionAccounts)
+        .preInstructions([await startSession()])
+        .signers([gameServer])
+        .rpc(),
+      "WinNotRevealed"
+    );
+
+    // A committed session pays nothing until it is revealed as a win
+    await program.methods
+      .commitGame(gameId, [...randomBytes(32)])
+      .accounts(sessionAccounts)
+      .preInstructions([await startSession()])
+      .signers([gameServer])
+      .rpc();
+    await expectError(
+      program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts({ ...accounts, gameSession })
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc(),
+      "GameNotWon"
+    );
+  });
+
+  it("Rejects a commitment made after the session started", async () => {
+    const player = Keypair.generate().publicKey;
+    const gameId = new BN(++lastGameId);
+    const gameSession = gameSessionPda(player, gameId);
+    const sessionAccounts = { config: configPda, gameServer: gameServer.publicKey, player, gameSession };
+
+    await program.methods
+      .startSession(gameId)
+      .accounts({ ...sessionAccounts, payer: payer.publicKey, systemProgram: anchor.web3.SystemProgram.programId })
+      .signers([gameServer])
+      .rpc();
+    const { startSlot } = await program.account.gameSession.fetch(gameSession);
+    while ((await provider.connection.getSlot()) <= startSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 100));
+    }
+
+    await expectError(
+      program.methods
+        .commitGame(gameId, [...randomBytes(32)])
+        .accounts(sessionAccounts)
+        .signers([gameServer])
+        .rpc(),
+      "CommitTooLate"
+    );
+  });
+
+  it("Keeps attested game results in the player's stats", async () => {
+    const player = Keypair.generate().publicKey;
+    const first = await playGame(player);
+    const second = await playGame(player, { lost: {} });
+
+    await recordGameResult(player, first.gameId, { correctGuesses: 1, correctEjections: 3, incorrectGuesses: 0, incorrectEjections: 0 });
+    await recordGameResult(player, second.gameId, {
+      correctGuesses: 0,
+      correctEjections: 2,
+      incorrectGuesses: 1,
+      incorrectEjections: 1,
+    });
+
+    const stats = await program.account.playerStats.fetch(playerStatsPda(player));
+    expect(stats.player.toBase58()).to.equal(player.toBase58());
+    expect(stats.correctGuesses.toNumber()).to.equal(1);
+    expect(stats.correctEjections.toNumber()).to.equal(5);
+    expect(stats.incorrectGuesses.toNumber()).to.equal(1);
+    expect(stats.incorrectEjections.toNumber()).to.equal(1);
+    expect(stats.gamesPlayed.toNumber()).to.equal(2);
+    expect(stats.lastUpdatedSlot.toNumber()).to.be.greaterThan(0);
+
+    await expectError(
+      recordGameResult(player, first.gameId, { correctGuesses: 1, correctEjections: 3, incorrectGuesses: 0, incorrectEjections: 0 }),
+      "ResultAlreadyRecorded"
+    );
+
+    const third = await playGame(player);
+    await expectError(
+      recordGameResult(
+        player,
+        third.gameId,
+        { correctGuesses: 100, correctEjections: 0, incorrectGuesses: 0, incorrectEjections: 0 },
+        Keypair.generate()
+      ),
+      "InvalidAttestationSigner"
+    );
+
+    // Still in progress
+    const gameId = new BN(++lastGameId);
+    await program.methods
+      .startSession(gameId)
+      .accounts({
+        config: configPda,
+        gameServer: gameServer.publicKey,
+        player,
+        gameSession: gameSessionPda(player, gameId),
+        payer: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([gameServer])
+      .rpc();
+    await expectError(
+      recordGameResult(player, gameId, { correctGuesses: 1, correctEjections: 0, incorrectGuesses: 0, incorrectEjections: 0 }),
+      "SessionNotEnded"
+    );
+  });
+
+  it("Ranks players on the leaderboards, earliest first on ties", async () => {
+    const [first, second, third] = [Keypair.generate(), Keypair.generate(), Keypair.generate()].map(
+      (player) => player.publicKey
+    );
+    const record = async (player: PublicKey, correctGuesses: number, correctEjections: number) => {
+      const { gameId } = await playGame(player);
+      await recordGameResult(player, gameId, { correctGuesses, correctEjections, incorrectGuesses: 0, incorrectEjections: 0 });
+    };
+
+    await record(first, 5, 1);
+    await record(second, 5, 2);
+    await record(third, 7, 0);
+
+    const rank = async (leaderboard: PublicKey) =>
+      (await program.account.leaderboard.fetch(leaderboard)).entries
+        .map((entry) => entry.player.toBase58())
+        .filter((player) => [first, second, third].some((key) => key.toBase58() === player));
+
+    expect(await rank(guessesLeaderboard)).to.deep.equal([third, first, second].map((key) => key.toBase58()));
+    // No correct ejections, so no place on that board
+    expect(await rank(ejectionsLeaderboard)).to.deep.equal([second, first].map((key) => key.toBase58()));
+
+    // Passing the leader moves the first player to the top
+    await record(first, 3, 0);
+    expect(await rank(guessesLeaderboard)).to.deep.equal([first, third, second].map((key) => key.toBase58()));
+  });
+
+  it("Lets only the admin reset the leaderboards", async () => {
+    const other = Keypair.generate();
+    const accounts = { config: configPda, guessesLeaderboard, ejectionsLeaderboard };
+
+    await expectError(
+      program.methods
+        .resetLeaderboards()
+        .accounts({ ...accounts, admin: other.publicKey })
+        .signers([other])
+        .rpc(),
+      "Unauthorized"
+    );
+
+    const { season } = await program.account.leaderboard.fetch(guessesLeaderboard);
+    await program.methods
+      .resetLeaderboards()
+      .accounts({ ...accounts, admin: payer.publicKey })
+      .rpc();
+
+    for (const leaderboard of [guessesLeaderboard, ejectionsLeaderboard]) {
+      const board = await program.account.leaderboard.fetch(leaderboard);
+      expect(board.entries).to.be.empty;
+      expect(board.season).to.equal(season + 1);
+    }
+  });
+
+  it("Ranks only the current season's games after a reset", async () => {
+    const player = Keypair.generate().publicKey;
+    const record = async (correctGuesses: number) => {
+      const { gameId } = await playGame(player);
+      await recordGameResult(player, gameId, { correctGuesses, correctEjections: 0, incorrectGuesses: 0, incorrectEjections: 0 });
+    };
+
+    await record(4);
+    await program.methods
+      .resetLeaderboards()
+      .accounts({ config: configPda, guessesLeaderboard, ejectionsLeaderboard, admin: payer.publicKey })
+      .rpc();
+    await record(1);
+
+    const board = await program.account.leaderboard.fetch(guessesLeaderboard);
+    const entry = board.entries.find((e) => e.player.toBase58() === player.toBase58());
+    expect(entry.score.toNumber()).to.equal(1);
+
+    const stats = await program.account.playerStats.fetch(playerStatsPda(player));
+    expect(stats.correctGuesses.toNumber()).to.equal(5);
+    expect(stats.seasonCorrectGuesses.toNumber()).to.equal(1);
+    expect(stats.season).to.equal(board.season);
+  });
+
+  it("Only mints for a won game session, once", async () => {
+    const metadataUri = "https://placeholder.metadata/session_planet";
+    const expiry = inOneHour();
+
+    const mintWithSession = async (planetId: string, nonce: BN, gameId: BN, gameSession: PublicKey) => {
+      const { accounts, mint } = await mintAccounts(planetId, payer.publicKey);
+      return program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts({ ...accounts, gameSession })
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+    };
+
+    const lost = await playGame(payer.publicKey, { lost: {} });
+    await expectError(
+      mintWithSession("lost_planet", new BN(28), lost.gameId, lost.gameSession),
+      "GameNotWon"
+    );
+
+    const won = await playGame(payer.publicKey);
+    await mintWithSession("won_planet", new BN(29), won.gameId, won.gameSession);
+    expect((await program.account.gameSession.fetch(won.gameSession)).rewardClaimed).to.be.true;
+
+    // A different planet for the same win
+    await expectError(
+      mintWithSession("second_won_planet", new BN(30), won.gameId, won.gameSession),
+      "RewardAlreadyClaimed"
+    );
+  });
+
+  it("Only mints the planet picked in the game session", async () => {
+    const planetId = "unpicked_planet";
+    const metadataUri = "https://placeholder.metadata/unpicked_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(35);
+
+    // The session was won by picking seed 42; seed 7 is a valid planet too
+    const otherSeed = new BN(7);
+    const other = generatePlanet(otherSeed);
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+    await expectError(
+      program.methods
+        .mintPlanetNft({
+          planetId,
+          seed: otherSeed,
+          planetName: other.planetName,
+          metadataUri,
+          gameId,
+          expiry,
+          nonce,
+          soulbound: false,
+          stats: other.stats,
         })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, otherSeed, metadataUri, expiry, nonce),
+        ])
+        .rpc(),
+      "PlanetNotSelected"
+    );
+  });
+
+  it("Refuses to mint while paused", async () => {
+    const planetId = "paused_planet";
+    const metadataUri = "https://placeholder.metadata/paused_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(7);
+
+    const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+    await program.methods
+      .setPaused(true)
+      .accounts({ config: configPda, admin: payer.publicKey })
+      .rpc();
+
+    try {
+      await expectError(
+        program.methods
+          .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+          .accounts(accounts)
+        .signers([mint])
+          .preInstructions([
+            attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+          ])
+          .rpc(),
+        "ProgramPaused"
+      );
+    } finally {
+      await program.methods
+        .setPaused(false)
+        .accounts({ config: configPda, admin: payer.publicKey })
         .rpc();
+    }
+  });
 
-      console.log("Transaction signature:", tx);
-      console.log("Mint PDA:", mintPda.toString());
-    } catch (err) {
-      console.error("Error:", err);
-      throw err;
+  it("Only lets the admin change the config", async () => {
+    const intruder = Keypair.generate();
+
+    await expectError(
+      program.methods
+        .updateConfig({ attestationSigner: intruder.publicKey })
+        .accounts({ config: configPda, admin: intruder.publicKey })
+        .signers([intruder])
+        .rpc(),
+      "Unauthorized"
+    );
+
+    await expectError(
+      program.methods
+        .setPaused(true)
+        .accounts({ config: configPda, admin: intruder.publicKey })
+        .signers([intruder])
+        .rpc(),
+      "Unauthorized"
+    );
+
+    const config = await program.account.config.fetch(configPda);
+    expect(config.attestationSigner.toBase58()).to.equal(gameServer.publicKey.toBase58());
+    expect(config.paused).to.equal(false);
+  });
+
+  it("Hands admin over in two steps", async () => {
+    const newAdmin = Keypair.generate();
+    const wrongAdmin = Keypair.generate();
+
+    await program.methods
+      .proposeAdmin(newAdmin.publicKey)
+      .accounts({ config: configPda, admin: payer.publicKey })
+      .rpc();
+
+    // Only the proposed key can accept
+    await expectError(
+      program.methods
+        .acceptAdmin()
+        .accounts({ config: configPda, pendingAdmin: wrongAdmin.publicKey })
+        .signers([wrongAdmin])
+        .rpc(),
+      "NotPendingAdmin"
+    );
+
+    await program.methods
+      .acceptAdmin()
+      .accounts({ config: configPda, pendingAdmin: newAdmin.publicKey })
+      .signers([newAdmin])
+      .rpc();
+
+    let config = await program.account.config.fetch(configPda);
+    expect(config.admin.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+    expect(config.pendingAdmin).to.equal(null);
+
+    // Hand it back so the remaining tests can keep using the provider wallet
+    await program.methods
+      .proposeAdmin(payer.publicKey)
+      .accounts({ config: configPda, admin: newAdmin.publicKey })
+      .signers([newAdmin])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ config: configPda, pendingAdmin: payer.publicKey })
+      .rpc();
+
+    config = await program.account.config.fetch(configPda);
+    expect(config.admin.toBase58()).to.equal(payer.publicKey.toBase58());
+  });
+
+  it("Lets the admin cancel a pending proposal", async () => {
+    const typo = Keypair.generate();
+
+    await program.methods
+      .proposeAdmin(typo.publicKey)
+      .accounts({ config: configPda, admin: payer.publicKey })
+      .rpc();
+    await program.methods
+      .cancelAdminProposal()
+      .accounts({ config: configPda, admin: payer.publicKey })
+      .rpc();
+
+    await expectError(
+      program.methods
+        .acceptAdmin()
+        .accounts({ config: configPda, pendingAdmin: typo.publicKey })
+        .signers([typo])
+        .rpc(),
+      "NoPendingAdmin"
+    );
+  });
+
+  it("Burns a planet and lets it be claimed again", async () => {
+    const planetId = "burnt_planet";
+    const metadataUri = "https://placeholder.metadata/burnt_planet";
+    const expiry = inOneHour();
+
+    const mintBurnable = async (nonce: BN) => {
+      const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+      await program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+      return accounts;
+    };
+
+    const accounts = await mintBurnable(new BN(16));
+
+    await program.methods
+      .burnPlanet()
+      .accounts({
+        owner: payer.publicKey,
+        mint: accounts.mint,
+        tokenAccount: accounts.tokenAccount,
+        playerPlanet: accounts.playerPlanet,
+        planetState: accounts.planetState,
+        metadata: accounts.metadata,
+        masterEdition: accounts.masterEdition,
+        collectionMetadata: accounts.collectionMetadata,
+        tokenMetadataProgram: METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    for (const closed of [
+      accounts.tokenAccount,
+      accounts.metadata,
+      accounts.masterEdition,
+      accounts.playerPlanet,
+      accounts.planetState,
+    ]) {
+      expect(await provider.connection.getAccountInfo(closed)).to.be.null;
     }
+
+    const again = await mintBurnable(new BN(17));
+    const record = await program.account.playerPlanet.fetch(again.playerPlanet);
+    expect(record.mint.toBase58()).to.equal(again.mint.toBase58());
+  });
+
+  it("Burns a planet that has no PlayerPlanet record", async () => {
+    // Migrated from the fixtures earlier; legacy planets have no record and
+    // no master edition
+    const [legacyMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("planet_nft"), Buffer.from("legacy_planet")],
+      program.programId
+    );
+    const tokenAccount = await getAssociatedTokenAddress(legacyMint, legacyOwner.publicKey);
+
+    await program.methods
+      .burnPlanet()
+      .accounts({
+        owner: legacyOwner.publicKey,
+        mint: legacyMint,
+        tokenAccount,
+        playerPlanet: null,
+        planetState: null,
+        metadata: metadataPda(legacyMint),
+        masterEdition: masterEditionPda(legacyMint),
+        collectionMetadata: metadataPda(collectionMint),
+        tokenMetadataProgram: METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([legacyOwner])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(tokenAccount)).to.be.null;
+    expect(Number((await getMint(provider.connection, legacyMint)).supply)).to.equal(0);
+  });
+
+  it("Freezes soulbound planets until the admin thaws them", async () => {
+    const planetId = "first_win_planet";
+    const metadataUri = "https://placeholder.metadata/first_win_planet";
+    const expiry = inOneHour();
+    const player = Keypair.generate();
+
+    const mintSoulbound = async (nonce: BN, signers: Keypair[]) => {
+      const { accounts, mint, gameId } = await mintAccounts(planetId, player.publicKey);
+      await program.methods
+        .mintPlanetNft({
+          planetId,
+          seed,
+          planetName,
+          metadataUri,
+          gameId,
+          expiry,
+          nonce,
+          soulbound: true,
+          stats,
+        })
+        .accounts(accounts)
+        .signers([mint, ...signers])
+        .preInstructions([
+          attest(gameServer, planetId, player.publicKey, gameId, seed, metadataUri, expiry, nonce, true),
+        ])
+        .rpc();
+      return accounts;
+    };
+
+    // The recipient has to approve the freeze
+    await expectError(mintSoulbound(new BN(18), []), "RecipientMustSign");
+
+    const accounts = await mintSoulbound(new BN(19), [player]);
+    let account = await getAccount(provider.connection, accounts.tokenAccount);
+    expect(account.isFrozen).to.be.true;
+
+    await program.methods
+      .thawPlanet(planetId)
+      .accounts({
+        config: configPda,
+        admin: payer.publicKey,
+        mintAuthority: accounts.mintAuthority,
+        mint: accounts.mint,
+        tokenAccount: accounts.tokenAccount,
+        metadata: accounts.metadata,
+        masterEdition: accounts.masterEdition,
+        tokenMetadataProgram: METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    account = await getAccount(provider.connection, accounts.tokenAccount);
+    expect(account.isFrozen).to.be.false;
+  });
+
+  const token2022Accounts = async (
+    accounts: { playerPlanet: PublicKey; gameSession: PublicKey; mintAuthority: PublicKey },
+    mint: PublicKey
+  ) => ({
+    config: configPda,
+    treasury: treasuryPda,
+    playerPlanet: accounts.playerPlanet,
+    gameSession: accounts.gameSession,
+    mint,
+    planetState: PublicKey.findProgramAddressSync(
+      [Buffer.from("planet_state"), mint.toBuffer()],
+      program.programId
+    )[0],
+    mintAuthority: accounts.mintAuthority,
+    recipient: payer.publicKey,
+    tokenAccount: await getAssociatedTokenAddress(mint, payer.publicKey, false, TOKEN_2022_PROGRAM_ID),
+    payer: payer.publicKey,
+    instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+    // Optional token payment accounts; the SOL fee applies when left out
+    paymentMint: null,
+    payerPaymentAccount: null,
+    treasuryPaymentAccount: null,
+    paymentTokenProgram: null,
+    systemProgram: anchor.web3.SystemProgram.programId,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+  });
+
+  it("Mints a Token-2022 planet with metadata in the mint", async () => {
+    const planetId = "token_2022_planet";
+    const metadataUri = "https://placeholder.metadata/token_2022_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(20);
+
+    const mint = Keypair.generate();
+    const { accounts, gameId } = await mintAccounts(planetId, payer.publicKey);
+    const mintAccountsFor2022 = await token2022Accounts(accounts, mint.publicKey);
+
+    await program.methods
+      .mintPlanetNft2022({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+      .accounts(mintAccountsFor2022)
+      .signers([mint])
+      .preInstructions([
+        attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+      ])
+      .rpc();
+
+    const account = await getAccount(
+      provider.connection,
+      mintAccountsFor2022.tokenAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(Number(account.amount)).to.equal(1);
+
+    const mintInfo = await getMint(
+      provider.connection,
+      mint.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(mintInfo.mintAuthority).to.be.null;
+
+    const planetState = await program.account.planetState.fetch(mintAccountsFor2022.planetState);
+    expect(planetState.seed.eq(seed)).to.be.true;
+    expect(planetState.stats).to.deep.equal(stats);
+
+    // Written from the checked stats of seed 42
+    const metadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(metadata.name).to.equal(planetName);
+    expect(metadata.uri).to.equal(metadataUri);
+    expect(metadata.additionalMetadata).to.deep.equal([
+      ["temperature", "4°F"],
+      ["ocean_coverage", "83%"],
+      ["gravity", "0.72g"],
+      ["color", "Maroon red with black craters"],
+    ]);
+  });
+
+  it("Rejects a Token-2022 planet whose attributes don't match the seed", async () => {
+    const planetId = "forged_token_2022_planet";
+    const metadataUri = "https://placeholder.metadata/forged_token_2022_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(33);
+
+    const mint = Keypair.generate();
+    const { accounts, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+    await expectError(
+      program.methods
+        .mintPlanetNft2022({
+          planetId,
+          seed,
+          planetName,
+          metadataUri,
+          gameId,
+          expiry,
+          nonce,
+          soulbound: false,
+          stats: { ...stats, temperatureF: 72 },
+        })
+        .accounts(await token2022Accounts(accounts, mint.publicKey))
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc(),
+      "TemperatureMismatch"
+    );
+  });
+
+  it("Freezes soulbound Token-2022 planets until the admin thaws them", async () => {
+    const planetId = "soulbound_token_2022_planet";
+    const metadataUri = "https://placeholder.metadata/soulbound_token_2022_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(36);
+
+    const mint = Keypair.generate();
+    const { accounts, gameId } = await mintAccounts(planetId, payer.publicKey);
+    const mintAccountsFor2022 = await token2022Accounts(accounts, mint.publicKey);
+
+    await program.methods
+      .mintPlanetNft2022({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: true, stats })
+      .accounts(mintAccountsFor2022)
+      .signers([mint])
+      .preInstructions([
+        attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce, true),
+      ])
+      .rpc();
+
+    const tokenAccount = mintAccountsFor2022.tokenAccount;
+    let account = await getAccount(provider.connection, tokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(account.isFrozen).to.be.true;
+
+    await program.methods
+      .thawPlanet2022(planetId)
+      .accounts({
+        config: configPda,
+        admin: payer.publicKey,
+        mintAuthority: accounts.mintAuthority,
+        mint: mint.publicKey,
+        tokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    account = await getAccount(provider.connection, tokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(account.isFrozen).to.be.false;
+  });
+
+  it("Burns a Token-2022 planet and closes its mint", async () => {
+    const planetId = "burnt_token_2022_planet";
+    const metadataUri = "https://placeholder.metadata/burnt_token_2022_planet";
+    const expiry = inOneHour();
+    const nonce = new BN(40);
+
+    const mint = Keypair.generate();
+    const { accounts, gameId } = await mintAccounts(planetId, payer.publicKey);
+    const mintAccountsFor2022 = await token2022Accounts(accounts, mint.publicKey);
+
+    await program.methods
+      .mintPlanetNft2022({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+      .accounts(mintAccountsFor2022)
+      .signers([mint])
+      .preInstructions([
+        attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+      ])
+      .rpc();
+
+    await program.methods
+      .burnPlanet2022()
+      .accounts({
+        owner: payer.publicKey,
+        mint: mint.publicKey,
+        tokenAccount: mintAccountsFor2022.tokenAccount,
+        playerPlanet: mintAccountsFor2022.playerPlanet,
+        planetState: mintAccountsFor2022.planetState,
+        mintAuthority: accounts.mintAuthority,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    for (const closed of [
+      mint.publicKey,
+      mintAccountsFor2022.tokenAccount,
+      mintAccountsFor2022.playerPlanet,
+      mintAccountsFor2022.planetState,
+    ]) {
+      expect(await provider.connection.getAccountInfo(closed)).to.be.null;
+    }
+  });
+
+  describe("mint fee", () => {
+    const fee = new BN(anchor.web3.LAMPORTS_PER_SOL / 100);
+
+    before(async () => {
+      await program.methods
+        .updateConfig({ attestationSigner: null, mintFeeLamports: fee })
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateConfig({ attestationSigner: null, mintFeeLamports: new BN(0) })
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+    });
+
+    it("Pays the mint fee into the treasury", async () => {
+      const planetId = "fee_planet";
+      const metadataUri = "https://placeholder.metadata/fee_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(10);
+
+      const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+      const before = await provider.connection.getBalance(treasuryPda);
+
+      await program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+
+      const after = await provider.connection.getBalance(treasuryPda);
+      expect(after - before).to.equal(fee.toNumber());
+    });
+
+    it("Fails cleanly when the payer can't cover the fee", async () => {
+      const planetId = "broke_planet";
+      const metadataUri = "https://placeholder.metadata/broke_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(11);
+      const player = Keypair.generate();
+
+      // Enough for the accounts' rent, not enough for the fee on top
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: payer.publicKey,
+            toPubkey: player.publicKey,
+            lamports: fee.toNumber(),
+          })
+        )
+      );
+
+      const { accounts, mint, gameId } = await mintAccounts(
+        planetId,
+        player.publicKey,
+        player.publicKey
+      );
+
+      await expectError(
+        program.methods
+          .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+          .accounts(accounts)
+          .signers([mint, player])
+          .preInstructions([
+            attest(gameServer, planetId, player.publicKey, gameId, seed, metadataUri, expiry, nonce),
+          ])
+          .rpc(),
+        "InsufficientFundsForFee"
+      );
+    });
+
+    it("Only lets the admin withdraw from the treasury", async () => {
+      const intruder = Keypair.generate();
+
+      await expectError(
+        program.methods
+          .withdrawTreasury(fee)
+          .accounts({
+            config: configPda,
+            treasury: treasuryPda,
+            admin: intruder.publicKey,
+            destination: intruder.publicKey,
+          })
+          .signers([intruder])
+          .rpc(),
+        "Unauthorized"
+      );
+    });
+
+    it("Withdraws fees but keeps the treasury rent exempt", async () => {
+      const destination = Keypair.generate().publicKey;
+      const balance = await provider.connection.getBalance(treasuryPda);
+
+      await expectError(
+        program.methods
+          .withdrawTreasury(new BN(balance))
+          .accounts({
+            config: configPda,
+            treasury: treasuryPda,
+            admin: payer.publicKey,
+            destination,
+          })
+          .rpc(),
+        "InsufficientTreasuryBalance"
+      );
+
+      await program.methods
+        .withdrawTreasury(fee)
+        .accounts({
+          config: configPda,
+          treasury: treasuryPda,
+          admin: payer.publicKey,
+          destination,
+        })
+        .rpc();
+
+      expect(await provider.connection.getBalance(destination)).to.equal(fee.toNumber());
+    });
+  });
+
+  describe("token payment", () => {
+    const price = new BN(5_000_000); // 5 tokens with 6 decimals
+    let paymentMint: PublicKey;
+    let payerPaymentAccount: PublicKey;
+    let treasuryPaymentAccount: PublicKey;
+
+    before(async () => {
+      const wallet = (payer as anchor.Wallet).payer;
+      paymentMint = await createMint(provider.connection, wallet, payer.publicKey, null, 6);
+      payerPaymentAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          wallet,
+          paymentMint,
+          payer.publicKey
+        )
+      ).address;
+      treasuryPaymentAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          wallet,
+          paymentMint,
+          treasuryPda,
+          true
+        )
+      ).address;
+      await mintTo(
+        provider.connection,
+        wallet,
+        paymentMint,
+        payerPaymentAccount,
+        payer.publicKey,
+        100_000_000
+      );
+    });
+
+    it("Rejects a token that isn't on the allowlist", async () => {
+      const planetId = "usdc_planet";
+      const metadataUri = "https://placeholder.metadata/usdc_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(12);
+
+      const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+      await expectError(
+        program.methods
+          .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+          .accounts({ ...accounts, paymentMint, payerPaymentAccount, treasuryPaymentAccount })
+          .signers([mint])
+          .preInstructions([
+            attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+          ])
+          .rpc(),
+        "PaymentMintNotAllowed"
+      );
+    });
+
+    it("Pays the mint fee in an allowlisted token", async () => {
+      const planetId = "usdc_planet";
+      const metadataUri = "https://placeholder.metadata/usdc_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(13);
+
+      await program.methods
+        .setPaymentMint(paymentMint, price)
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+
+      const { accounts, mint, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+      await program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts({ ...accounts, paymentMint, payerPaymentAccount, treasuryPaymentAccount })
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+
+      const treasuryAccount = await getAccount(provider.connection, treasuryPaymentAccount);
+      expect(Number(treasuryAccount.amount)).to.equal(price.toNumber());
+    });
+
+    it("Pays the Token-2022 mint fee in an allowlisted token", async () => {
+      const planetId = "usdc_token_2022_planet";
+      const metadataUri = "https://placeholder.metadata/usdc_token_2022_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(37);
+
+      const mint = Keypair.generate();
+      const { accounts, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+      await program.methods
+        .mintPlanetNft2022({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts({
+          ...(await token2022Accounts(accounts, mint.publicKey)),
+          paymentMint,
+          payerPaymentAccount,
+          treasuryPaymentAccount,
+          paymentTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+
+      // On top of the fee paid by the Metaplex mint above
+      const treasuryAccount = await getAccount(provider.connection, treasuryPaymentAccount);
+      expect(Number(treasuryAccount.amount)).to.equal(price.muln(2).toNumber());
+    });
+
+    it("Lets the admin withdraw token fees", async () => {
+      await program.methods
+        .withdrawTreasuryTokens(price.muln(2))
+        .accounts({
+          config: configPda,
+          treasury: treasuryPda,
+          admin: payer.publicKey,
+          paymentMint,
+          treasuryPaymentAccount,
+          destination: payerPaymentAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const treasuryAccount = await getAccount(provider.connection, treasuryPaymentAccount);
+      expect(Number(treasuryAccount.amount)).to.equal(0);
+
+      await program.methods
+        .removePaymentMint(paymentMint)
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+      const config = await program.account.config.fetch(configPda);
+      expect(config.paymentMints).to.have.length(0);
+    });
+  });
+
+  describe("royalties", () => {
+    const studio = Keypair.generate().publicKey;
+    const royalties = {
+      attestationSigner: null,
+      mintFeeLamports: null,
+      studio,
+      sellerFeeBasisPoints: 500,
+      discovererShare: 20,
+      maxPrints: null,
+      printFeeLamports: null,
+    };
+
+    before(async () => {
+      await program.methods
+        .updateConfig(royalties)
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .updateConfig({ ...royalties, studio: null, sellerFeeBasisPoints: 0, discovererShare: 0 })
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+    });
+
+    it("Rejects a royalty above 100%", async () => {
+      await expectError(
+        program.methods
+          .updateConfig({ ...royalties, sellerFeeBasisPoints: 10_001 })
+          .accounts({ config: configPda, admin: payer.publicKey })
+          .rpc(),
+        "InvalidSellerFee"
+      );
+    });
+
+    it("Lists the program, studio and discoverer as creators", async () => {
+      const planetId = "royalty_planet";
+      const metadataUri = "https://placeholder.metadata/royalty_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(14);
+      const discoverer = Keypair.generate();
+
+      const { accounts, mint, gameId } = await mintAccounts(planetId, discoverer.publicKey);
+
+      await program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, discoverer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(accounts.metadata);
+      const { sellerFeeBasisPoints, creators } = decodeMetadata(info.data);
+      expect(sellerFeeBasisPoints).to.equal(500);
+      expect(
+        creators.map((c) => [c.address.toBase58(), c.verified, c.share])
+      ).to.deep.equal([
+        [programAuthority.toBase58(), true, 0],
+        [studio.toBase58(), false, 80],
+        [discoverer.publicKey.toBase58(), false, 20],
+      ]);
+    });
+  });
+
+  describe("compressed planets", () => {
+    const merkleTree = Keypair.generate();
+    const [treeConfig] = PublicKey.findProgramAddressSync(
+      [merkleTree.publicKey.toBuffer()],
+      BUBBLEGUM_PROGRAM_ID
+    );
+    const [bubblegumSigner] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collection_cpi")],
+      BUBBLEGUM_PROGRAM_ID
+    );
+
+    // TreeConfig layout: discriminator, creator, delegate, capacity, num_minted
+    const readTreeConfig = async () => {
+      const { data } = await provider.connection.getAccountInfo(treeConfig);
+      return {
+        treeDelegate: new PublicKey(data.subarray(40, 72)),
+        numMinted: new BN(data.subarray(80, 88), "le"),
+      };
+    };
+
+    const compressedAccounts = (
+      accounts: { playerPlanet: PublicKey; gameSession: PublicKey },
+      tree: PublicKey
+    ) => ({
+      config: configPda,
+      treasury: treasuryPda,
+      playerPlanet: accounts.playerPlanet,
+      gameSession: accounts.gameSession,
+      programAuthority,
+      treeConfig: PublicKey.findProgramAddressSync([tree.toBuffer()], BUBBLEGUM_PROGRAM_ID)[0],
+      merkleTree: tree,
+      recipient: payer.publicKey,
+      collectionMint,
+      collectionMetadata: metadataPda(collectionMint),
+      collectionMasterEdition: masterEditionPda(collectionMint),
+      bubblegumSigner,
+      payer: payer.publicKey,
+      instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      logWrapper: NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      // Optional token payment accounts; the SOL fee applies when left out
+      paymentMint: null,
+      payerPaymentAccount: null,
+      treasuryPaymentAccount: null,
+      paymentTokenProgram: null,
+      tokenMetadataProgram: METADATA_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+
+    const delegateAccounts = (admin: PublicKey, newTreeDelegate: PublicKey) => ({
+      config: configPda,
+      admin,
+      programAuthority,
+      treeConfig,
+      merkleTree: merkleTree.publicKey,
+      newTreeDelegate,
+      bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+
+    before(async () => {
+      const maxDepth = 14;
+      const maxBufferSize = 64;
+      const allocTree = await createAllocTreeIx(
+        provider.connection,
+        merkleTree.publicKey,
+        payer.publicKey,
+        { maxDepth, maxBufferSize },
+        0
+      );
+
+      await program.methods
+        .createPlanetTree(maxDepth, maxBufferSize)
+        .accounts({
+          config: configPda,
+          admin: payer.publicKey,
+          programAuthority,
+          treeConfig,
+          merkleTree: merkleTree.publicKey,
+          logWrapper: NOOP_PROGRAM_ID,
+          compressionProgram: COMPRESSION_PROGRAM_ID,
+          bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .preInstructions([allocTree])
+        .signers([merkleTree])
+        .rpc();
+    });
+
+    it("Mints a compressed planet into the program's tree", async () => {
+      const planetId = "compressed_planet";
+      const metadataUri = "https://placeholder.metadata/compressed_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(21);
+
+      const { accounts, gameId } = await mintAccounts(planetId, payer.publicKey);
+      const { numMinted } = await readTreeConfig();
+
+      await program.methods
+        .mintCompressedPlanet({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, stats })
+        .accounts(compressedAccounts(accounts, merkleTree.publicKey))
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+
+      const [assetId] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("asset"),
+          merkleTree.publicKey.toBuffer(),
+          numMinted.toArrayLike(Buffer, "le", 8),
+        ],
+        BUBBLEGUM_PROGRAM_ID
+      );
+      const record = await program.account.playerPlanet.fetch(accounts.playerPlanet);
+      expect(record.mint.toBase58()).to.equal(assetId.toBase58());
+      expect((await readTreeConfig()).numMinted.toNumber()).to.equal(
+        numMinted.toNumber() + 1
+      );
+    });
+
+    it("Pays the compressed mint fee in an allowlisted token", async () => {
+      const planetId = "usdc_compressed_planet";
+      const metadataUri = "https://placeholder.metadata/usdc_compressed_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(41);
+      const price = new BN(5_000_000);
+
+      const wallet = (payer as anchor.Wallet).payer;
+      const paymentMint = await createMint(provider.connection, wallet, payer.publicKey, null, 6);
+      const payerPaymentAccount = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, wallet, paymentMint, payer.publicKey)
+      ).address;
+      const treasuryPaymentAccount = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, wallet, paymentMint, treasuryPda, true)
+      ).address;
+      await mintTo(provider.connection, wallet, paymentMint, payerPaymentAccount, payer.publicKey, price.toNumber());
+      await program.methods
+        .setPaymentMint(paymentMint, price)
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+
+      const { accounts, gameId } = await mintAccounts(planetId, payer.publicKey);
+      await program.methods
+        .mintCompressedPlanet({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, stats })
+        .accounts({
+          ...compressedAccounts(accounts, merkleTree.publicKey),
+          paymentMint,
+          payerPaymentAccount,
+          treasuryPaymentAccount,
+          paymentTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .preInstructions([
+          attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+
+      const treasuryAccount = await getAccount(provider.connection, treasuryPaymentAccount);
+      expect(Number(treasuryAccount.amount)).to.equal(price.toNumber());
+
+      await program.methods
+        .removePaymentMint(paymentMint)
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+    });
+
+    it("Rejects a compressed planet whose name doesn't match the seed", async () => {
+      const planetId = "forged_compressed_planet";
+      const metadataUri = "https://placeholder.metadata/forged_compressed_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(34);
+      const { accounts, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+      await expectError(
+        program.methods
+          .mintCompressedPlanet({
+            planetId,
+            seed,
+            planetName: "Compressed Planet",
+            metadataUri,
+            gameId,
+            expiry,
+            nonce,
+            stats,
+          })
+          .accounts(compressedAccounts(accounts, merkleTree.publicKey))
+          .preInstructions([
+            attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+          ])
+          .rpc(),
+        "PlanetNameMismatch"
+      );
+    });
+
+    it("Only mints into trees registered by create_planet_tree", async () => {
+      const config = await program.account.config.fetch(configPda);
+      expect(config.planetTrees.map((t) => t.toBase58())).to.include(
+        merkleTree.publicKey.toBase58()
+      );
+
+      const planetId = "foreign_tree_planet";
+      const metadataUri = "https://placeholder.metadata/foreign_tree_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(32);
+      const { accounts, gameId } = await mintAccounts(planetId, payer.publicKey);
+
+      await expectError(
+        program.methods
+          .mintCompressedPlanet({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, stats })
+          .accounts(compressedAccounts(accounts, Keypair.generate().publicKey))
+          .preInstructions([
+            attest(gameServer, planetId, payer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+          ])
+          .rpc(),
+        "UnknownPlanetTree"
+      );
+    });
+
+    it("Only lets the admin delegate the tree", async () => {
+      const intruder = Keypair.generate();
+      await expectError(
+        program.methods
+          .setPlanetTreeDelegate()
+          .accounts(delegateAccounts(intruder.publicKey, intruder.publicKey))
+          .signers([intruder])
+          .rpc(),
+        "Unauthorized"
+      );
+
+      await program.methods
+        .setPlanetTreeDelegate()
+        .accounts(delegateAccounts(payer.publicKey, gameServer.publicKey))
+        .rpc();
+      const { treeDelegate } = await readTreeConfig();
+      expect(treeDelegate.toBase58()).to.equal(gameServer.publicKey.toBase58());
+    });
+  });
+
+  describe("badges", () => {
+    const badgeId = "flawless_win";
+    const [badgeKind] = PublicKey.findProgramAddressSync(
+      [Buffer.from("badge_kind"), Buffer.from(badgeId)],
+      program.programId
+    );
+    const player = Keypair.generate();
+
+    const claimAccounts = async () => {
+      const [badgeMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("badge"), Buffer.from(badgeId), player.publicKey.toBuffer()],
+        program.programId
+      );
+      return {
+        config: configPda,
+        badgeKind,
+        playerStats: playerStatsPda(player.publicKey),
+        player: player.publicKey,
+        badgeMint,
+        tokenAccount: await getAssociatedTokenAddress(badgeMint, player.publicKey),
+        metadata: metadataPda(badgeMint),
+        masterEdition: masterEditionPda(badgeMint),
+        programAuthority,
+        tokenMetadataProgram: METADATA_PROGRAM_ID,
+        payer: payer.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      };
+    };
+
+    it("Lets only the admin define badges", async () => {
+      const args = {
+        badgeId,
+        name: "Flawless Win",
+        uri: "https://placeholder.metadata/badges/flawless_win",
+        stat: { flawlessWins: {} },
+        threshold: new BN(1),
+      };
+      const accounts = { config: configPda, badgeKind, systemProgram: anchor.web3.SystemProgram.programId };
+
+      const other = Keypair.generate();
+      await expectError(
+        program.methods
+          .createBadgeKind(args)
+          .accounts({ ...accounts, admin: other.publicKey })
+          .signers([other])
+          .rpc(),
+        "Unauthorized"
+      );
+
+      await program.methods
+        .createBadgeKind(args)
+        .accounts({ ...accounts, admin: payer.publicKey })
+        .rpc();
+    });
+
+    it("Mints a frozen badge once the player reaches the milestone", async () => {
+      // A win with a wrong ejection doesn't count
+      const { gameId } = await playGame(player.publicKey);
+      await recordGameResult(player.publicKey, gameId, {
+        correctGuesses: 1,
+        correctEjections: 4,
+        incorrectGuesses: 0,
+        incorrectEjections: 1,
+      });
+      const stats = await program.account.playerStats.fetch(playerStatsPda(player.publicKey));
+      expect(stats.wins.toNumber()).to.equal(1);
+      expect(stats.flawlessWins.toNumber()).to.equal(0);
+
+      const accounts = await claimAccounts();
+      await expectError(
+        program.methods.claimBadge(badgeId).accounts(accounts).signers([player]).rpc(),
+        "BadgeMilestoneNotReached"
+      );
+
+      const flawless = await playGame(player.publicKey);
+      await recordGameResult(player.publicKey, flawless.gameId, {
+        correctGuesses: 1,
+        correctEjections: 5,
+        incorrectGuesses: 0,
+        incorrectEjections: 0,
+      });
+
+      await program.methods.claimBadge(badgeId).accounts(accounts).signers([player]).rpc();
+
+      const account = await getAccount(provider.connection, accounts.tokenAccount);
+      expect(Number(account.amount)).to.equal(1);
+      expect(account.isFrozen).to.be.true;
+
+      const info = await provider.connection.getAccountInfo(accounts.metadata);
+      const metadata = decodeMetadata(info.data);
+      expect(metadata.symbol).to.equal("BADGE");
+      expect(metadata.name).to.equal("Flawless Win");
+      expect(metadata.creators[0].address.toBase58()).to.equal(programAuthority.toBase58());
+      expect(metadata.creators[0].verified).to.be.true;
+
+      expect((await program.account.badgeKind.fetch(badgeKind)).claimed.toNumber()).to.equal(1);
+
+      // The badge mint is a PDA of the badge and player, so it can't be
+      // claimed twice
+      let claimedTwice = false;
+      try {
+        await program.methods.claimBadge(badgeId).accounts(accounts).signers([player]).rpc();
+        claimedTwice = true;
+      } catch (err) {
+        // Expected: the badge mint already exists
+      }
+      expect(claimedTwice).to.be.false;
+    });
+  });
+
+  describe("print editions", () => {
+    const planetId = "print_planet";
+    const printFee = new BN(anchor.web3.LAMPORTS_PER_SOL / 100);
+    const discoverer = Keypair.generate();
+    const friend = Keypair.generate();
+    let masterMint: PublicKey;
+
+    // Metaplex keeps one edition marker per 248 prints
+    const editionMarkPda = (mint: PublicKey, edition: number) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("metadata"),
+          METADATA_PROGRAM_ID.toBuffer(),
+          mint.toBuffer(),
+          Buffer.from("edition"),
+          Buffer.from(Math.floor(edition / 248).toString()),
+        ],
+        METADATA_PROGRAM_ID
+      )[0];
+
+    const printAccounts = async (recipient: PublicKey, edition: number) => {
+      const newMint = Keypair.generate();
+      const [playerPlanet] = PublicKey.findProgramAddressSync(
+        [Buffer.from("player_planet"), discoverer.publicKey.toBuffer(), Buffer.from(planetId)],
+        program.programId
+      );
+      const [mintAuthority] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint_authority"), Buffer.from(planetId)],
+        program.programId
+      );
+      const accounts = {
+        config: configPda,
+        playerPlanet,
+        discoverer: discoverer.publicKey,
+        holder: discoverer.publicKey,
+        holderTokenAccount: await getAssociatedTokenAddress(masterMint, discoverer.publicKey),
+        masterMint,
+        masterMetadata: metadataPda(masterMint),
+        masterEdition: masterEditionPda(masterMint),
+        mintAuthority,
+        recipient,
+        newMint: newMint.publicKey,
+        newTokenAccount: await getAssociatedTokenAddress(newMint.publicKey, recipient),
+        newMetadata: metadataPda(newMint.publicKey),
+        newEdition: masterEditionPda(newMint.publicKey),
+        editionMarkPda: editionMarkPda(masterMint, edition),
+        programAuthority,
+        payer: friend.publicKey,
+        tokenMetadataProgram: METADATA_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      };
+      return { newMint, accounts };
+    };
+
+    before(async () => {
+      await program.methods
+        .updateConfig({ attestationSigner: null, maxPrints: new BN(1), printFeeLamports: printFee })
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: payer.publicKey,
+            toPubkey: friend.publicKey,
+            lamports: anchor.web3.LAMPORTS_PER_SOL,
+          })
+        )
+      );
+
+      const metadataUri = "https://placeholder.metadata/print_planet";
+      const expiry = inOneHour();
+      const nonce = new BN(31);
+      const { accounts, mint, gameId } = await mintAccounts(planetId, discoverer.publicKey);
+      await program.methods
+        .mintPlanetNft({ planetId, seed, planetName, metadataUri, gameId, expiry, nonce, soulbound: false, stats })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([
+          attest(gameServer, planetId, discoverer.publicKey, gameId, seed, metadataUri, expiry, nonce),
+        ])
+        .rpc();
+      masterMint = accounts.mint;
+    });
+
+    after(async () => {
+      await program.methods
+        .updateConfig({ attestationSigner: null, maxPrints: new BN(0), printFeeLamports: new BN(0) })
+        .accounts({ config: configPda, admin: payer.publicKey })
+        .rpc();
+    });
+
+    it("Prints a numbered edition for a friend and pays the discoverer", async () => {
+      const { newMint, accounts } = await printAccounts(friend.publicKey, 1);
+      const before = await provider.connection.getBalance(discoverer.publicKey);
+
+      await program.methods
+        .printPlanetEdition(planetId)
+        .accounts(accounts)
+        .signers([discoverer, friend, newMint])
+        .rpc();
+
+      const after = await provider.connection.getBalance(discoverer.publicKey);
+      expect(after - before).to.equal(printFee.toNumber());
+
+      const account = await getAccount(provider.connection, accounts.newTokenAccount);
+      expect(account.owner.toBase58()).to.equal(friend.publicKey.toBase58());
+      expect(Number(account.amount)).to.equal(1);
+
+      const info = await provider.connection.getAccountInfo(accounts.newMetadata);
+      expect(decodeMetadata(info.data).name).to.equal(planetName);
+    });
+
+    it("Stops printing at the configured maximum", async () => {
+      const { newMint, accounts } = await printAccounts(friend.publicKey, 2);
+      await expectError(
+        program.methods
+          .printPlanetEdition(planetId)
+          .accounts(accounts)
+          .signers([discoverer, friend, newMint])
+          .rpc(),
+        "PrintLimitReached"
+      );
+    });
   });
 });
@@ -6,6 +6,7 @@ Applies SQL migration files to the database
 import asyncio
 import asyncpg
 import os
+import sys
 from pathlib import Path
 from dotenv import load_dotenv
 
@@ -44,7 +45,8 @@ async def run_migration(migration_file: str):
 
 async def main():
     """Main function to run the migration"""
-    migration_file = "001_create_planet_nfts.sql"
+    # e.g. `python run_migration.py 002_add_planet_nft_attestations.sql`
+    migration_file = sys.argv[1] if len(sys.argv) > 1 else "001_create_planet_nfts.sql"
     
     print("=" * 60)
     print("Database Migration Runner")
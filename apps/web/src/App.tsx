@@ -679,9 +679,9 @@ function App() {
 
                     // Award NFT for winning
                     try {
-                        // Generate unique planet ID based on planet name and timestamp
-                        const planetId = `${planet.planetName.toLowerCase().replace(/\s+/g, "_")}_${Date.now()}`;
-                        await earnNFT(user.email, planetId, planet.planetName);
+                        // One planet ID per generated planet, within the program's 32-byte limit
+                        const planetId = `planet_${planet.seed ?? Date.now()}`;
+                        await earnNFT(user.email, planetId, planet);
                     } catch (error) {
                         console.error("Failed to earn NFT:", error);
                         // Don't block game flow if NFT earning fails
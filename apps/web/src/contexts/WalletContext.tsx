@@ -14,7 +14,7 @@ import { SolflareWalletAdapter } from "@solana/wallet-adapter-solflare";
 import { clusterApiUrl } from "@solana/web3.js";
 import type { Transaction, VersionedTransaction } from "@solana/web3.js";
 import { solanaService } from "../services/solana";
-import { getUnmintedNFTs, updateMintInfo } from "../services/api";
+import { getMintAttestation, getUnmintedNFTs, updateMintInfo } from "../services/api";
 import type { Wallet as AnchorWallet } from "@coral-xyz/anchor";
 
 // Import wallet adapter CSS
@@ -86,15 +86,11 @@ function WalletContextInner({ children }: { children: ReactNode }) {
             // Mint each NFT
             for (const nft of unmintedNFTs) {
                 try {
-                    // Prepare metadata
-                    const metadata = {
-                        planet_id: nft.planet_id,
-                        planet_name: nft.planet_name,
-                        earned_date: nft.earned_date,
-                    };
+                    // Get the game server's signed go-ahead for this wallet
+                    const attestation = await getMintAttestation(nft.id, publicKey.toBase58());
 
                     // Mint NFT
-                    const result = await solanaService.mintPlanetNFT(anchorWallet, nft.planet_id, nft.planet_name, metadata);
+                    const result = await solanaService.mintPlanetNFT(anchorWallet, attestation);
 
                     // Update backend with mint info
                     await updateMintInfo(nft.id, result.tokenId, result.signature, result.metadataUri);
@@ -7,6 +7,7 @@ export interface Voice {
   planetColor: string;
   oceanCoverage: string;
   gravity: string;
+  seed?: string; // u64 planet-generator seed, only on generated planets
   isResearcher?: boolean;
   correctFacts?: string[]; // Which facts this fake researcher knows correctly
 }
@@ -1,6 +1,7 @@
 import { StrictMode } from "react";
 import { createRoot } from "react-dom/client";
 import { Auth0Provider, useAuth0 } from "@auth0/auth0-react";
+import initPlanetGenerator from "planet-generator-wasm";
 import App from "./App.tsx";
 import LoginScreen from "./features/auth/LoginScreen.tsx";
 import { UserProvider } from "./contexts/UserContext.tsx";
@@ -41,16 +42,20 @@ export function AppWithAuth() {
     );
 }
 
-createRoot(document.getElementById("root")!).render(
-    <StrictMode>
-        <Auth0Provider
-            domain={import.meta.env.VITE_AUTH0_DOMAIN}
-            clientId={import.meta.env.VITE_AUTH0_CLIENT_ID}
-            authorizationParams={{
-                redirect_uri: window.location.origin,
-            }}
-        >
-            <AppWithAuth />
-        </Auth0Provider>
-    </StrictMode>
-);
+// App generates its first planets on the first render, so the planet
+// generator has to be loaded before anything renders
+initPlanetGenerator().then(() => {
+    createRoot(document.getElementById("root")!).render(
+        <StrictMode>
+            <Auth0Provider
+                domain={import.meta.env.VITE_AUTH0_DOMAIN}
+                clientId={import.meta.env.VITE_AUTH0_CLIENT_ID}
+                authorizationParams={{
+                    redirect_uri: window.location.origin,
+                }}
+            >
+                <AppWithAuth />
+            </Auth0Provider>
+        </StrictMode>
+    );
+});
@@ -98,6 +98,42 @@ export interface PlanetNFT {
   metadata_uri: string | null;
 }
 
+/**
+ * Game server's go-ahead to mint an earned planet, see "Mint attestations" in
+ * apps/api/planet-nft/README.md. u64 fields are decimal strings.
+ */
+export interface MintAttestation {
+  planet_id: string;
+  seed: string;
+  planet_name: string;
+  metadata_uri: string;
+  game_id: string;
+  expiry: number;
+  nonce: string;
+  soulbound: boolean;
+  stats: {
+    temperature_f: number;
+    ocean_coverage: number;
+    gravity_centi_g: number;
+    color: string;
+  };
+  /** Base64 ed25519 signature of the Borsh-encoded attestation */
+  signature: string;
+}
+
+/**
+ * Planet a player won. The game server needs the seed and stats to sign the
+ * mint attestation; planets without a seed can't be minted.
+ */
+export interface EarnedPlanet {
+  planetName: string;
+  seed?: string;
+  planetColor: string;
+  avgTemp: string;
+  oceanCoverage: string;
+  gravity: string;
+}
+
 export interface UpdateMintInfoRequest {
   token_id: string;
   mint_signature: string;
@@ -373,7 +409,7 @@ export async function getGameSessionWithChats(gameId: string): Promise<GameSessi
 export async function earnNFT(
   email: string,
   planetId: string,
-  planetName: string
+  planet: EarnedPlanet
 ): Promise<PlanetNFT> {
   const response = await fetch(`${API_BASE_URL}/api/nfts/earn`, {
     method: 'POST',
@@ -381,7 +417,12 @@ export async function earnNFT(
     body: JSON.stringify({
       player_email: email,
       planet_id: planetId,
-      planet_name: planetName,
+      planet_name: planet.planetName,
+      seed: planet.seed,
+      planet_color: planet.planetColor,
+      avg_temp: planet.avgTemp,
+      ocean_coverage: planet.oceanCoverage,
+      gravity: planet.gravity,
     }),
   });
 
@@ -418,6 +459,26 @@ export async function getUnmintedNFTs(email: string): Promise<PlanetNFT[]> {
   return response.json();
 }
 
+/**
+ * Get the signed attestation needed to mint an earned NFT to a wallet
+ */
+export async function getMintAttestation(
+  nftId: number,
+  wallet: string
+): Promise<MintAttestation> {
+  const response = await fetch(`${API_BASE_URL}/api/nfts/${nftId}/attestation`, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify({ wallet }),
+  });
+
+  if (!response.ok) {
+    throw new Error(`Failed to get mint attestation: ${response.statusText}`);
+  }
+
+  return response.json();
+}
+
 /**
  * Update NFT with mint information after successful minting
  */
@@ -1,23 +1,24 @@
 /**
  * Solana Service
  * Handles NFT minting and Solana blockchain interactions
- *
- * Note: This is a simplified version that will be extended when the Anchor program is deployed.
- * For now, it provides the structure and can be used for testing the flow.
  */
 import {
   Connection,
+  Ed25519Program,
+  Keypair,
   PublicKey,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
   SYSVAR_RENT_PUBKEY,
-  Transaction,
 } from "@solana/web3.js";
 import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
-import { Program, AnchorProvider } from "@coral-xyz/anchor";
+import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
 import type { Wallet, Idl } from "@coral-xyz/anchor";
+import type { MintAttestation } from "./api";
 import type {
   IdlInstructionDef,
   IdlAccountDef,
@@ -27,8 +28,6 @@ import type {
   SeedDetail,
   PdaAccountInfo,
   MintAccounts,
-  RpcError,
-  DemoError,
   SolanaError,
 } from "./types";
 
@@ -40,14 +39,49 @@ const PROGRAM_ID = new PublicKey(
   "Fb7uNXapsRwUdsvGDedesLS7D1A4AHk6CeMvrrvTVqwf"
 );
 
-export interface PlanetNFTMetadata {
-  planet_id: string;
-  planet_name: string;
-  planet_color?: string;
-  avg_temp?: string;
-  ocean_coverage?: string;
-  gravity?: string;
-  earned_date?: string;
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+function findProgramPda(...seeds: Buffer[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, PROGRAM_ID)[0];
+}
+
+// Metaplex metadata PDA, or its master edition PDA with the "edition" suffix
+function findMetadataPda(mint: PublicKey, ...suffix: Buffer[]): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      ...suffix,
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
+
+// Borsh encoding of the program's MintAttestation struct, the message the
+// game server signed
+function encodeMintAttestation(
+  attestation: MintAttestation,
+  recipient: PublicKey
+): Buffer {
+  const str = (value: string) => {
+    const bytes = Buffer.from(value);
+    const len = Buffer.alloc(4);
+    len.writeUInt32LE(bytes.length);
+    return Buffer.concat([len, bytes]);
+  };
+  return Buffer.concat([
+    str(attestation.planet_id),
+    recipient.toBuffer(),
+    new BN(attestation.game_id).toArrayLike(Buffer, "le", 8),
+    new BN(attestation.seed).toArrayLike(Buffer, "le", 8),
+    str(attestation.metadata_uri),
+    new BN(attestation.expiry).toArrayLike(Buffer, "le", 8),
+    new BN(attestation.nonce).toArrayLike(Buffer, "le", 8),
+    Buffer.from([attestation.soulbound ? 1 : 0]),
+  ]);
 }
 
 // Type guard for IDL with instructions
@@ -191,16 +225,6 @@ export class SolanaService {
     }
   }
 
-  /**
-   * Derive Program Derived Address (PDA) for a planet NFT mint
-   */
-  deriveMintPDA(planetId: string): [PublicKey, number] {
-    return PublicKey.findProgramAddressSync(
-      [Buffer.from("planet_nft"), Buffer.from(planetId)],
-      PROGRAM_ID
-    );
-  }
-
   /**
    * Derive mint authority PDA
    * Uses seeds: [b"mint_authority", planet_id] (fixed in deployed program)
@@ -223,120 +247,117 @@ export class SolanaService {
   }
 
   /**
-   * Upload metadata to backend (which will handle IPFS/Arweave upload)
-   * For now, returns a placeholder URI
-   */
-  async uploadMetadata(metadata: PlanetNFTMetadata): Promise<string> {
-    const API_BASE_URL =
-      import.meta.env.VITE_API_URL || "http://localhost:8000";
-
-    try {
-      const response = await fetch(`${API_BASE_URL}/api/nfts/upload-metadata`, {
-        method: "POST",
-        headers: { "Content-Type": "application/json" },
-        body: JSON.stringify(metadata),
-      });
-
-      if (!response.ok) {
-        throw new Error(`Failed to upload metadata: ${response.statusText}`);
-      }
-
-      const data = await response.json();
-      return data.uri;
-    } catch (error) {
-      console.warn("Metadata upload failed, using placeholder:", error);
-      // Return placeholder URI for now
-      return `https://placeholder.metadata/${metadata.planet_id}`;
-    }
-  }
-
-  /**
-   * Mint a planet NFT using the deployed Anchor program
+   * Mint an earned planet NFT using the deployed Anchor program. The game
+   * server's attestation is checked by an ed25519 instruction right before
+   * the mint, and every copy of a planet gets a fresh mint keypair.
    */
   async mintPlanetNFT(
     wallet: Wallet,
-    planetId: string,
-    planetName: string,
-    metadata: PlanetNFTMetadata
+    attestation: MintAttestation
   ): Promise<{ tokenId: string; signature: string; metadataUri: string }> {
     try {
-      // Step 1: Upload metadata
-      const metadataUri = await this.uploadMetadata(metadata);
-
-      // Step 2: Initialize program (force refresh to get latest IDL)
+      // Step 1: Initialize program (force refresh to get latest IDL)
       const program = await this.initializeProgram(wallet, true);
 
-      // Step 3: Derive mint PDA
-      const [mintPda] = this.deriveMintPDA(planetId);
+      const planetId = attestation.planet_id;
+      const recipient = wallet.publicKey;
+      const gameId = new BN(attestation.game_id);
+      const mint = Keypair.generate();
 
-      // Step 3.5: Derive mint authority PDA (uses correct seeds: [b"mint_authority", planet_id])
-      const [mintAuthorityPda] =
-        this.deriveMintAuthorityPDA(planetId);
+      // Step 2: Derive program PDAs
+      const config = findProgramPda(Buffer.from("config"));
+      const [mintAuthorityPda] = this.deriveMintAuthorityPDA(planetId);
+      const collectionMint = findProgramPda(Buffer.from("collection"));
 
-      // Verify PDAs are different (they should be!)
-      if (mintPda.toBase58() === mintAuthorityPda.toBase58()) {
-        console.warn(
-          "WARNING: mint and mint_authority PDAs are the same! This suggests IDL is incorrect."
-        );
-      }
-
-      // Step 4: Get associated token account
+      // Step 3: Get associated token account
       const tokenAccount = await this.getAssociatedTokenAccount(
-        mintPda,
-        wallet.publicKey
+        mint.publicKey,
+        recipient
       );
 
-      // Step 5: Derive metadata account (Metaplex standard)
-      const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
-        "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
-      );
-      const [metadataAccount] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("metadata"),
-          TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-          mintPda.toBuffer(),
-        ],
-        TOKEN_METADATA_PROGRAM_ID
-      );
+      // Step 4: Rebuild the attested message for the ed25519 instruction,
+      // signed by the config's attestation signer
+      const { attestationSigner } = (await program.account.config.fetch(
+        config
+      )) as { attestationSigner: PublicKey };
+      const attestationIx = Ed25519Program.createInstructionWithPublicKey({
+        publicKey: attestationSigner.toBytes(),
+        message: encodeMintAttestation(attestation, recipient),
+        signature: Buffer.from(attestation.signature, "base64"),
+      });
 
-      // Step 6: Log IDL structure for debugging
+      // Step 5: Log IDL structure for debugging
       this.logIdlStructure(program);
 
-      // Step 7: Build accounts for the mint instruction
+      // Step 6: Build accounts for the mint instruction. The optional token
+      // payment accounts are left out, so the SOL mint fee is charged.
       const accounts: MintAccounts = {
-        mint: mintPda,
-        mint_authority: mintAuthorityPda,
-        token_account: tokenAccount,
-        metadata: metadataAccount,
-        token_metadata_program: TOKEN_METADATA_PROGRAM_ID,
+        config,
+        treasury: findProgramPda(Buffer.from("treasury")),
+        playerPlanet: findProgramPda(
+          Buffer.from("player_planet"),
+          recipient.toBuffer(),
+          Buffer.from(planetId)
+        ),
+        gameSession: findProgramPda(
+          Buffer.from("game_session"),
+          recipient.toBuffer(),
+          gameId.toArrayLike(Buffer, "le", 8)
+        ),
+        mint: mint.publicKey,
+        planetState: findProgramPda(
+          Buffer.from("planet_state"),
+          mint.publicKey.toBuffer()
+        ),
+        mintAuthority: mintAuthorityPda,
+        recipient,
+        tokenAccount,
+        metadata: findMetadataPda(mint.publicKey),
+        masterEdition: findMetadataPda(mint.publicKey, Buffer.from("edition")),
+        programAuthority: findProgramPda(Buffer.from("authority")),
+        collectionMint,
+        collectionMetadata: findMetadataPda(collectionMint),
+        collectionMasterEdition: findMetadataPda(
+          collectionMint,
+          Buffer.from("edition")
+        ),
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         payer: wallet.publicKey,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
         rent: SYSVAR_RENT_PUBKEY,
-        system_program: SystemProgram.programId,
-        token_program: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       };
 
-      // Call Anchor program with .rpc()
-      try {
-        const signature = await program.methods
-          .mintPlanetNft(planetId, planetName, metadataUri)
-          .accounts(accounts)
-          .rpc();
-
-        return {
-          tokenId: mintPda.toBase58(),
-          signature,
-          metadataUri,
-        };
-      } catch (rpcError) {
-        const error = rpcError as RpcError;
-        console.warn(
-          ".rpc() failed, trying simplified approach:",
-          error.message
-        );
+      const { stats } = attestation;
+      const signature = await program.methods
+        .mintPlanetNft({
+          planetId,
+          seed: new BN(attestation.seed),
+          planetName: attestation.planet_name,
+          metadataUri: attestation.metadata_uri,
+          gameId,
+          expiry: new BN(attestation.expiry),
+          nonce: new BN(attestation.nonce),
+          soulbound: attestation.soulbound,
+          stats: {
+            temperatureF: stats.temperature_f,
+            oceanCoverage: stats.ocean_coverage,
+            gravityCentiG: stats.gravity_centi_g,
+            color: stats.color,
+          },
+        })
+        .accounts(accounts)
+        .signers([mint])
+        .preInstructions([attestationIx])
+        .rpc();
 
-        // For hackathon demo: Use a simplified approach that simulates minting
-        return await this.createDemoTransaction(wallet, mintPda, metadataUri);
-      }
+      return {
+        tokenId: mint.publicKey.toBase58(),
+        signature,
+        metadataUri: attestation.metadata_uri,
+      };
     } catch (error) {
       const solanaError = error as SolanaError;
       console.error("Error minting NFT:", solanaError.message);
@@ -423,72 +444,6 @@ export class SolanaService {
     }
   }
 
-  /**
-   * Create demo transaction for hackathon purposes
-   */
-  private async createDemoTransaction(
-    wallet: Wallet,
-    mintPda: PublicKey,
-    metadataUri: string
-  ): Promise<{ tokenId: string; signature: string; metadataUri: string }> {
-    try {
-      const { blockhash, lastValidBlockHeight } =
-        await this.connection.getLatestBlockhash("confirmed");
-
-      // Create a minimal transaction for demo
-      const transaction = new Transaction();
-      transaction.recentBlockhash = blockhash;
-      transaction.feePayer = wallet.publicKey;
-
-      // Zero transfer just for signature
-      transaction.add(
-        SystemProgram.transfer({
-          fromPubkey: wallet.publicKey,
-          toPubkey: wallet.publicKey,
-          lamports: 0,
-        })
-      );
-
-      // Sign and send transaction
-      const signedTx = await wallet.signTransaction(transaction);
-      const sig = await this.connection.sendRawTransaction(
-        signedTx.serialize(),
-        {
-          skipPreflight: false,
-          maxRetries: 3,
-        }
-      );
-
-      // Confirm transaction
-      await this.connection.confirmTransaction(
-        {
-          signature: sig,
-          blockhash,
-          lastValidBlockHeight,
-        },
-        "confirmed"
-      );
-
-      return {
-        tokenId: mintPda.toBase58(),
-        signature: sig,
-        metadataUri,
-      };
-    } catch (demoError) {
-      const error = demoError as DemoError;
-      console.error("Demo transaction failed:", error.message);
-
-      // Last resort: Return mock data for demo
-      const mockSignature = "mock_" + Date.now().toString(36);
-
-      return {
-        tokenId: mintPda.toBase58(),
-        signature: mockSignature,
-        metadataUri,
-      };
-    }
-  }
-
   /**
    * Sign a message for wallet verification
    */
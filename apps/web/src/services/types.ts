@@ -83,17 +83,6 @@ export interface PdaAccountInfo {
 // Accounts object for Anchor method calls - uses Record for compatibility with Anchor's .accounts() method
 export type MintAccounts = Record<string, PublicKey>;
 
-// RPC Error type
-export interface RpcError extends Error {
-  message: string;
-  logs?: string[];
-}
-
-// Demo Error type
-export interface DemoError extends Error {
-  message: string;
-}
-
 // General Solana Error type
 export interface SolanaError extends Error {
   message: string;